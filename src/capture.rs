@@ -0,0 +1,254 @@
+//! Recording the changes made to an inventory so they can be replayed.
+//!
+//! Recording is done with the `capture` feature, which is on by default.
+//! Without it `InventoryCapture` still works but does not record anything.
+use std::ops::Deref;
+
+use crate::errors::InventoryError;
+use crate::inventory::InventoryIndex;
+use crate::inventory_management::{
+    add_to_inventory, remove_from_inventory, sort_inventory_by, ItemSort,
+};
+use crate::slot_management::move_slot_in_inventory;
+use crate::traits::{Item, ItemInstance, Slot};
+
+/// A change made to an inventory through `InventoryCapture`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum InventoryOp<II> {
+    /// The inventory was created with these slots.
+    Init(Vec<Option<II>>),
+    /// See `add_to_inventory`.
+    Add(II),
+    /// See `remove_from_inventory`.
+    Remove(II),
+    /// See `move_slot_in_inventory`.
+    Move { from: usize, to: usize },
+    /// The items in two slots were swapped.
+    Swap { a: usize, b: usize },
+    /// See `sort_inventory_by`.
+    Sort(ItemSort),
+    /// The item instance of a slot was set.
+    Set {
+        index: usize,
+        item_instance: Option<II>,
+    },
+}
+
+/// Wraps an inventory and records every change made to it as an `InventoryOp`.
+///
+/// Changes go through the methods, the inventory can only be read through `Deref`.
+/// Operations that fail are recorded too, since replaying them fails the same way.
+/// ```
+/// # use game_inventory::samples::{DefaultItem, DefaultItemInstance, DefaultSlot, TORCH_INST, SWORD_INST};
+/// # use game_inventory::capture::{replay, InventoryCapture};
+/// # use game_inventory::traits::Slot;
+/// # #[cfg(feature = "capture")] {
+/// type TestSlot<'a> = DefaultSlot<'a, DefaultItem<'a>, DefaultItemInstance<DefaultItem<'a>>>;
+/// let mut capture: InventoryCapture<TestSlot, _> =
+///     InventoryCapture::new(vec![DefaultSlot::new(None), DefaultSlot::new(None)]);
+/// capture.add_item(TORCH_INST.clone().unwrap());
+/// capture.set_item(1, SWORD_INST.clone()).unwrap();
+/// capture.swap_slots(0, 1).unwrap();
+/// assert_eq!(capture.ops().len(), 4);
+///
+/// let replayed: Vec<TestSlot> = replay(capture.ops());
+/// assert_eq!(replayed[0].item_id(), Some("sword"));
+/// assert_eq!(replayed[1].quantity(), 23);
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct InventoryCapture<S, II> {
+    inner: Vec<S>,
+    ops: Vec<InventoryOp<II>>,
+}
+
+impl<S, II: Clone> InventoryCapture<S, II> {
+    /// Starts recording changes to the inventory.
+    pub fn new<Id: Eq, I: Item<Id = Id>>(slots: Vec<S>) -> Self
+    where
+        II: ItemInstance<I>,
+        S: Slot<I, II>,
+    {
+        let mut capture = InventoryCapture {
+            inner: slots,
+            ops: Vec::new(),
+        };
+        capture
+            .record(|inner| InventoryOp::Init(inner.iter().map(|s| s.item_instance()).collect()));
+        capture
+    }
+
+    /// Every change recorded so far, starting with `InventoryOp::Init`.
+    pub fn ops(&self) -> &[InventoryOp<II>] {
+        &self.ops
+    }
+
+    /// See `add_to_inventory`.
+    pub fn add_item<Id: Eq, I: Item<Id = Id>>(&mut self, other: II) -> Option<II>
+    where
+        II: ItemInstance<I>,
+        S: Slot<I, II>,
+    {
+        self.record(|_| InventoryOp::Add(other.clone()));
+        add_to_inventory(&mut self.inner, other)
+    }
+
+    /// See `remove_from_inventory`.
+    pub fn remove_item<Id: Eq, I: Item<Id = Id>>(&mut self, other: II) -> Option<II>
+    where
+        II: ItemInstance<I>,
+        S: Slot<I, II>,
+    {
+        self.record(|_| InventoryOp::Remove(other.clone()));
+        remove_from_inventory(&mut self.inner, other)
+    }
+
+    /// See `move_slot_in_inventory`.
+    pub fn move_slot<Id: Eq, I: Item<Id = Id>>(
+        &mut self,
+        from: impl Into<InventoryIndex>,
+        to: impl Into<InventoryIndex>,
+    ) -> Result<(), InventoryError>
+    where
+        II: ItemInstance<I>,
+        S: Slot<I, II>,
+    {
+        let (from, to) = (from.into().get(), to.into().get());
+        self.record(|_| InventoryOp::Move { from, to });
+        move_slot_in_inventory(&mut self.inner, from, to)
+    }
+
+    /// Swaps the items in two slots.
+    pub fn swap_slots<Id: Eq, I: Item<Id = Id>>(
+        &mut self,
+        a: impl Into<InventoryIndex>,
+        b: impl Into<InventoryIndex>,
+    ) -> Result<(), InventoryError>
+    where
+        II: ItemInstance<I>,
+        S: Slot<I, II>,
+    {
+        let (a, b) = (a.into().get(), b.into().get());
+        self.record(|_| InventoryOp::Swap { a, b });
+        swap_slots(&mut self.inner, a, b)
+    }
+
+    /// See `sort_inventory_by`.
+    pub fn sort<Id: Ord, I: Item<Id = Id>>(&mut self, sort: ItemSort)
+    where
+        II: ItemInstance<I>,
+        S: Slot<I, II>,
+    {
+        self.record(|_| InventoryOp::Sort(sort));
+        sort_inventory_by(&mut self.inner, sort)
+    }
+
+    /// Sets the item instance of the slot at `index`, returning the one that was there.
+    pub fn set_item<Id: Eq, I: Item<Id = Id>>(
+        &mut self,
+        index: impl Into<InventoryIndex>,
+        item_instance: Option<II>,
+    ) -> Result<Option<II>, InventoryError>
+    where
+        II: ItemInstance<I>,
+        S: Slot<I, II>,
+    {
+        let index = index.into().get();
+        self.record(|_| InventoryOp::Set {
+            index,
+            item_instance: item_instance.clone(),
+        });
+        set_item(&mut self.inner, index, &item_instance)
+    }
+
+    /// Gets the slots out of the inventory.
+    pub fn into_inner(self) -> Vec<S> {
+        self.inner
+    }
+
+    #[cfg(feature = "capture")]
+    fn record<F: FnOnce(&[S]) -> InventoryOp<II>>(&mut self, op: F) {
+        let op = op(&self.inner);
+        self.ops.push(op);
+    }
+
+    #[cfg(not(feature = "capture"))]
+    #[inline(always)]
+    fn record<F: FnOnce(&[S]) -> InventoryOp<II>>(&mut self, _op: F) {}
+}
+
+impl<S, II> Deref for InventoryCapture<S, II> {
+    type Target = Vec<S>;
+
+    fn deref(&self) -> &Vec<S> {
+        &self.inner
+    }
+}
+
+/// Rebuilds an inventory from the changes recorded by `InventoryCapture`.
+///
+/// Starts from an empty inventory, so `ops` should start with `InventoryOp::Init`.
+/// Operations that fail are skipped, the same as when they were recorded.
+pub fn replay<Id: Ord, I: Item<Id = Id>, II: ItemInstance<I> + Clone, S: Slot<I, II>>(
+    ops: &[InventoryOp<II>],
+) -> Vec<S> {
+    let mut inventory: Vec<S> = Vec::new();
+    for op in ops {
+        match op {
+            InventoryOp::Init(slots) => {
+                inventory = slots.iter().cloned().map(S::new).collect();
+            }
+            InventoryOp::Add(ii) => {
+                add_to_inventory(&mut inventory, ii.clone());
+            }
+            InventoryOp::Remove(ii) => {
+                remove_from_inventory(&mut inventory, ii.clone());
+            }
+            InventoryOp::Move { from, to } => {
+                let _ = move_slot_in_inventory(&mut inventory, *from, *to);
+            }
+            InventoryOp::Swap { a, b } => {
+                let _ = swap_slots(&mut inventory, *a, *b);
+            }
+            InventoryOp::Sort(sort) => sort_inventory_by(&mut inventory, *sort),
+            InventoryOp::Set {
+                index,
+                item_instance,
+            } => {
+                let _ = set_item(&mut inventory, *index, item_instance);
+            }
+        }
+    }
+    inventory
+}
+
+fn swap_slots<Id: Eq, I: Item<Id = Id>, II: ItemInstance<I>, S: Slot<I, II>>(
+    inventory: &mut [S],
+    a: usize,
+    b: usize,
+) -> Result<(), InventoryError> {
+    for index in [a, b] {
+        if index >= inventory.len() {
+            return Err(InventoryError::IndexOutOfBounds(index));
+        }
+    }
+    let item_a = inventory[a].item_instance();
+    let item_b = inventory[b].item_instance();
+    inventory[a].set_item_instance(&item_b);
+    inventory[b].set_item_instance(&item_a);
+    Ok(())
+}
+
+fn set_item<Id: Eq, I: Item<Id = Id>, II: ItemInstance<I>, S: Slot<I, II>>(
+    inventory: &mut [S],
+    index: usize,
+    item_instance: &Option<II>,
+) -> Result<Option<II>, InventoryError> {
+    let slot = inventory
+        .get_mut(index)
+        .ok_or(InventoryError::IndexOutOfBounds(index))?;
+    let old = slot.item_instance();
+    slot.set_item_instance(item_instance);
+    Ok(old)
+}
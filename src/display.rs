@@ -0,0 +1,35 @@
+//! Types for showing items in the UI, gated behind the `tooltip` feature.
+
+/// How a line of a tooltip should be drawn, see `Item::tooltip_lines`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TooltipStyle {
+    /// The name of the item, usually at the top in a highlighted color.
+    Header,
+    /// A stat of the item, like its damage or how many fit in a stack.
+    Stat,
+    /// The description of the item.
+    Description,
+    /// Lore text, usually in italics.
+    FlavorText,
+    /// Something the player should be warned about, like a cursed item.
+    Warning,
+}
+
+/// A single line of a tooltip.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TooltipLine {
+    pub text: String,
+    pub style: TooltipStyle,
+}
+
+impl TooltipLine {
+    /// Creates a line with the given style.
+    pub fn new(text: impl Into<String>, style: TooltipStyle) -> Self {
+        TooltipLine {
+            text: text.into(),
+            style,
+        }
+    }
+}
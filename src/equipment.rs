@@ -0,0 +1,153 @@
+//! The items a character has equipped, as opposed to carrying in their inventory.
+use crate::inventory_management::add_to_inventory;
+use crate::traits::{Item, ItemInstance, ItemValue, ItemWeight, Slot};
+
+/// A place on a character where an item can be equipped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum EquipSlot {
+    Head,
+    Chest,
+    Legs,
+    Feet,
+    MainHand,
+    OffHand,
+    Accessory,
+}
+
+/// A characters loadout, with one slot for each `EquipSlot` it has.
+///
+/// ```
+/// # use game_inventory::samples::{DefaultItem, DefaultItemInstance, DefaultSlot, SWORD_INST, TORCH_INST};
+/// # use game_inventory::equipment::{swap_equipment, EquipSlot, EquipmentSet};
+/// # use game_inventory::traits::Slot;
+/// type TestSlot<'a> = DefaultSlot<'a, DefaultItem<'a>, DefaultItemInstance<DefaultItem<'a>>>;
+/// let mut set: EquipmentSet<TestSlot> = EquipmentSet::new([EquipSlot::MainHand, EquipSlot::OffHand]);
+/// let mut inventory = vec![DefaultSlot::new(None)];
+/// assert!(!set.is_complete());
+///
+/// assert!(swap_equipment(&mut set, &mut inventory, EquipSlot::MainHand, TORCH_INST.clone().unwrap()).is_none());
+/// assert!(swap_equipment(&mut set, &mut inventory, EquipSlot::MainHand, SWORD_INST.clone().unwrap()).is_none());
+/// assert_eq!(set.get(EquipSlot::MainHand).unwrap().item_id(), Some("sword"));
+/// assert_eq!(inventory[0].quantity(), 23);
+/// assert_eq!(set.missing_slots(), vec![EquipSlot::OffHand]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct EquipmentSet<S> {
+    slots: Vec<(EquipSlot, S)>,
+}
+
+impl<S> EquipmentSet<S> {
+    /// Creates a set with an empty slot for each of `equip_slots`.
+    pub fn new<Id: Eq, I: Item<Id = Id>, II: ItemInstance<I>>(
+        equip_slots: impl IntoIterator<Item = EquipSlot>,
+    ) -> Self
+    where
+        S: Slot<I, II>,
+    {
+        EquipmentSet {
+            slots: equip_slots.into_iter().map(|e| (e, S::new(None))).collect(),
+        }
+    }
+
+    /// The slot for `equip_slot`, if the set has one.
+    pub fn get(&self, equip_slot: EquipSlot) -> Option<&S> {
+        self.slots
+            .iter()
+            .find(|(e, _)| *e == equip_slot)
+            .map(|(_, s)| s)
+    }
+
+    /// The slot for `equip_slot`, if the set has one.
+    pub fn get_mut(&mut self, equip_slot: EquipSlot) -> Option<&mut S> {
+        self.slots
+            .iter_mut()
+            .find(|(e, _)| *e == equip_slot)
+            .map(|(_, s)| s)
+    }
+
+    /// Iterates over every slot in the set.
+    pub fn iter(&self) -> impl Iterator<Item = &(EquipSlot, S)> {
+        self.slots.iter()
+    }
+
+    /// Whether every slot in the set has an item equipped.
+    pub fn is_complete<Id: Eq, I: Item<Id = Id>, II: ItemInstance<I>>(&self) -> bool
+    where
+        S: Slot<I, II>,
+    {
+        self.slots.iter().all(|(_, s)| s.is_occupied())
+    }
+
+    /// The slots in the set that have nothing equipped.
+    pub fn missing_slots<Id: Eq, I: Item<Id = Id>, II: ItemInstance<I>>(&self) -> Vec<EquipSlot>
+    where
+        S: Slot<I, II>,
+    {
+        self.slots
+            .iter()
+            .filter(|(_, s)| s.is_empty())
+            .map(|(e, _)| *e)
+            .collect()
+    }
+
+    /// The combined weight of everything equipped.
+    ///
+    /// Stacks count their quantity, unstackable items count as one item.
+    pub fn total_weight<Id: Eq, I: Item<Id = Id> + ItemWeight, II: ItemInstance<I>>(&self) -> f32
+    where
+        S: Slot<I, II>,
+    {
+        self.equipped()
+            .map(|ii| ii.item().weight() * item_count(&ii) as f32)
+            .sum()
+    }
+
+    /// The combined value of everything equipped.
+    ///
+    /// Stacks count their quantity, unstackable items count as one item.
+    pub fn total_value<Id: Eq, I: Item<Id = Id> + ItemValue, II: ItemInstance<I>>(&self) -> u64
+    where
+        S: Slot<I, II>,
+    {
+        self.equipped()
+            .map(|ii| ii.item().value() * item_count(&ii) as u64)
+            .sum()
+    }
+
+    fn equipped<Id: Eq, I: Item<Id = Id>, II: ItemInstance<I>>(
+        &self,
+    ) -> impl Iterator<Item = II> + '_
+    where
+        S: Slot<I, II>,
+    {
+        self.slots.iter().filter_map(|(_, s)| s.item_instance())
+    }
+}
+
+fn item_count<I: Item, II: ItemInstance<I>>(item_instance: &II) -> u16 {
+    if item_instance.item().stackable() {
+        item_instance.quant()
+    } else {
+        1
+    }
+}
+
+/// Equips an item, putting whatever was equipped before into `main_inventory`.
+///
+/// Returns the item that was equipped before if it did not fit in `main_inventory`,
+/// or `new_item` if the set has no slot for `equip_slot`.
+pub fn swap_equipment<Id: Eq, I: Item<Id = Id>, II: ItemInstance<I> + Clone, S: Slot<I, II>>(
+    set: &mut EquipmentSet<S>,
+    main_inventory: &mut [S],
+    equip_slot: EquipSlot,
+    new_item: II,
+) -> Option<II> {
+    let slot = match set.get_mut(equip_slot) {
+        None => return Some(new_item),
+        Some(s) => s,
+    };
+    let old = slot.item_instance();
+    slot.set_item_instance(&Some(new_item));
+    old.and_then(|old| add_to_inventory(main_inventory, old))
+}
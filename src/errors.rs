@@ -0,0 +1,395 @@
+//! Error types returned by the inventory system.
+use std::{
+    error::Error,
+    fmt::{Debug, Display},
+};
+
+/// Returned when an item instance would be given an invalid quantity.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum QuantityError {
+    /// The requested quantity is larger than the stack size of the item.
+    ExceedsMaxQuantity { quantity: u16, max_quant: u16 },
+    /// The requested quantity is smaller than the minimum of the items `quantity_range`.
+    BelowMinQuantity { quantity: u16, min_quant: u16 },
+    /// Tried to take more items out of an instance than it holds.
+    InsufficientQuantity { quantity: u16, requested: u16 },
+}
+
+impl Display for QuantityError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            QuantityError::ExceedsMaxQuantity {
+                quantity,
+                max_quant,
+            } => write!(
+                f,
+                "A quantity of {} exceeds the max quantity of {}.",
+                quantity, max_quant
+            ),
+            QuantityError::BelowMinQuantity {
+                quantity,
+                min_quant,
+            } => write!(
+                f,
+                "A quantity of {} is below the min quantity of {}.",
+                quantity, min_quant
+            ),
+            QuantityError::InsufficientQuantity {
+                quantity,
+                requested,
+            } => write!(
+                f,
+                "Cannot take {} items from an instance that has {}.",
+                requested, quantity
+            ),
+        }
+    }
+}
+
+impl Error for QuantityError {}
+
+/// Returned when an operation on an inventory or its slots cannot be completed.
+///
+/// Variants that are caused by another error return it from `source`.
+/// ```
+/// # use game_inventory::errors::{InventoryError, QuantityError};
+/// # use std::error::Error;
+/// let err: InventoryError = QuantityError::ExceedsMaxQuantity { quantity: 101, max_quant: 100 }.into();
+/// let source = err.source().unwrap();
+/// assert_eq!(source.to_string(), "A quantity of 101 exceeds the max quantity of 100.");
+/// assert!(source.source().is_none());
+/// assert!(InventoryError::InventoryFull.source().is_none());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum InventoryError {
+    /// The slot has no item in it to take.
+    SlotEmpty,
+    /// The slot already has an item in it.
+    SlotOccupied,
+    /// The index does not point to a slot in the inventory.
+    IndexOutOfBounds(usize),
+    /// There is no room left in the inventory.
+    InventoryFull,
+    /// An item instance was given an invalid quantity.
+    Quantity(QuantityError),
+    /// The items would go over the weight limit of the inventory.
+    TooHeavy,
+    /// The slot does not accept that item.
+    ItemNotAccepted,
+}
+
+impl Display for InventoryError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InventoryError::SlotEmpty => f.write_str("The slot is empty."),
+            InventoryError::SlotOccupied => f.write_str("The slot already holds an item."),
+            InventoryError::IndexOutOfBounds(index) => {
+                write!(f, "There is no slot at index {}.", index)
+            }
+            InventoryError::InventoryFull => f.write_str("The inventory is full."),
+            InventoryError::Quantity(_) => f.write_str("An item has an invalid quantity."),
+            InventoryError::TooHeavy => f.write_str("The inventory cannot hold that much weight."),
+            InventoryError::ItemNotAccepted => f.write_str("The slot does not accept that item."),
+        }
+    }
+}
+
+impl Error for InventoryError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            InventoryError::Quantity(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<QuantityError> for InventoryError {
+    fn from(err: QuantityError) -> Self {
+        InventoryError::Quantity(err)
+    }
+}
+
+impl InventoryError {
+    /// Attaches the index of the slot that caused the error.
+    /// ```
+    /// # use game_inventory::errors::InventoryError;
+    /// fn take(index: usize) -> Result<(), InventoryError> {
+    ///     let err = InventoryError::SlotEmpty.context(index);
+    ///     assert_eq!(err.to_string(), "Slot 3: The slot is empty.");
+    ///     Err(err)?
+    /// }
+    /// assert_eq!(take(3), Err(InventoryError::SlotEmpty));
+    /// ```
+    pub fn context(self, slot_index: usize) -> InventoryErrorWithContext {
+        InventoryErrorWithContext {
+            error: self,
+            slot_index,
+        }
+    }
+}
+
+/// Returned when a slot was expected to hold an item instance but was empty.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EmptySlotError;
+
+impl Display for EmptySlotError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("The slot is empty.")
+    }
+}
+
+impl Error for EmptySlotError {}
+
+impl From<EmptySlotError> for InventoryError {
+    fn from(_: EmptySlotError) -> Self {
+        InventoryError::SlotEmpty
+    }
+}
+
+/// An `InventoryError` along with the index of the slot that caused it, see `InventoryError::context`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct InventoryErrorWithContext {
+    pub error: InventoryError,
+    pub slot_index: usize,
+}
+
+impl Display for InventoryErrorWithContext {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Slot {}: {}", self.slot_index, self.error)
+    }
+}
+
+impl Error for InventoryErrorWithContext {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+impl From<InventoryErrorWithContext> for InventoryError {
+    fn from(err: InventoryErrorWithContext) -> Self {
+        err.error
+    }
+}
+
+/// Returned when the item held by a `CursorSlot` cannot be picked up or dropped.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CursorError {
+    /// The cursor is not holding anything to drop.
+    NothingHeld,
+    /// The cursor is already holding an item.
+    AlreadyHolding,
+    /// The slot has no item in it to pick up.
+    NothingToPickUp,
+    /// The slot does not accept the held item, or cannot hold that many of it.
+    ItemNotAccepted,
+}
+
+impl Display for CursorError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CursorError::NothingHeld => f.write_str("The cursor is not holding an item."),
+            CursorError::AlreadyHolding => f.write_str("The cursor is already holding an item."),
+            CursorError::NothingToPickUp => f.write_str("The slot has no item to pick up."),
+            CursorError::ItemNotAccepted => f.write_str("The slot cannot take the held item."),
+        }
+    }
+}
+
+impl Error for CursorError {}
+
+/// Returned when an item cannot be crafted, see `craft_item`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CraftError {
+    /// The inventory does not have enough of the ingredients.
+    InsufficientIngredients,
+    /// There is no room for the crafted item.
+    OutputInventoryFull,
+}
+
+impl Display for CraftError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CraftError::InsufficientIngredients => {
+                f.write_str("There are not enough ingredients to craft the item.")
+            }
+            CraftError::OutputInventoryFull => {
+                f.write_str("There is no room for the crafted item.")
+            }
+        }
+    }
+}
+
+impl Error for CraftError {}
+
+/// Returned when several items cannot be removed at once, see `batch_remove_from_inventory`.
+/// ```
+/// # use game_inventory::errors::BatchRemoveError;
+/// let err = BatchRemoveError::InsufficientStock("torch");
+/// assert_eq!(err.to_string(), "There are not enough of torch to remove.");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum BatchRemoveError<Id> {
+    /// The inventory does not hold enough of the item with this id.
+    InsufficientStock(Id),
+}
+
+impl<Id: Display> Display for BatchRemoveError<Id> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BatchRemoveError::InsufficientStock(id) => {
+                write!(f, "There are not enough of {} to remove.", id)
+            }
+        }
+    }
+}
+
+impl<Id: Debug + Display> Error for BatchRemoveError<Id> {}
+
+/// An unstackable item that is in more than one slot, see `verify_no_duplicate_non_stackables`.
+/// ```
+/// # use game_inventory::errors::DuplicateError;
+/// let err = DuplicateError { item_id: "sword", slot_indices: vec![0, 3] };
+/// assert_eq!(err.to_string(), "The unstackable item sword is in slots [0, 3].");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DuplicateError<Id> {
+    pub item_id: Id,
+    pub slot_indices: Vec<usize>,
+}
+
+impl<Id: Display> Display for DuplicateError<Id> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "The unstackable item {} is in slots {:?}.",
+            self.item_id, self.slot_indices
+        )
+    }
+}
+
+impl<Id: Debug + Display> Error for DuplicateError<Id> {}
+
+/// A problem found by `validate_inventory_strict`.
+/// ```
+/// # use game_inventory::errors::{DuplicateError, InventoryError, ValidationError};
+/// let err: ValidationError<&str> = DuplicateError { item_id: "sword", slot_indices: vec![0, 3] }.into();
+/// assert_eq!(err.to_string(), "The unstackable item sword is in slots [0, 3].");
+/// let err: ValidationError<&str> = ValidationError::Slot { index: 2, error: InventoryError::InventoryFull };
+/// assert_eq!(err.to_string(), "The slot at index 2 is invalid: The inventory is full.");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ValidationError<Id> {
+    /// The slot at `index` failed `validate_inventory`.
+    Slot { index: usize, error: InventoryError },
+    /// An unstackable item is in more than one slot.
+    Duplicate(DuplicateError<Id>),
+}
+
+impl<Id> From<DuplicateError<Id>> for ValidationError<Id> {
+    fn from(err: DuplicateError<Id>) -> Self {
+        ValidationError::Duplicate(err)
+    }
+}
+
+impl<Id: Display> Display for ValidationError<Id> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ValidationError::Slot { index, error } => {
+                write!(f, "The slot at index {} is invalid: {}", index, error)
+            }
+            ValidationError::Duplicate(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+impl<Id: Debug + Display> Error for ValidationError<Id> {}
+
+/// Returned when an `InventoryDiff` cannot be undone, see `apply_reverse`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ApplyError {
+    /// The diff changed a slot that is not in the inventory.
+    IndexOutOfBounds(usize),
+    /// The slot at this index no longer holds what the diff changed it to.
+    SlotChanged(usize),
+}
+
+impl Display for ApplyError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ApplyError::IndexOutOfBounds(index) => {
+                write!(f, "There is no slot at index {}.", index)
+            }
+            ApplyError::SlotChanged(index) => {
+                write!(
+                    f,
+                    "The slot at index {} has changed since the diff was made.",
+                    index
+                )
+            }
+        }
+    }
+}
+
+impl Error for ApplyError {}
+
+/// Returned when an inventory cannot be put into a new order, see `reorder_inventory`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ReorderError {
+    /// The new order does not have one entry for each slot.
+    LengthMismatch { expected: usize, found: usize },
+    /// The new order refers to a slot that is not in the inventory.
+    IndexOutOfBounds(usize),
+    /// The new order takes from the same slot more than once.
+    DuplicateIndex(usize),
+    /// The new order leaves out a slot that holds an item.
+    ItemLost(usize),
+}
+
+impl Display for ReorderError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ReorderError::LengthMismatch { expected, found } => write!(
+                f,
+                "The new order has {} entries, but the inventory has {} slots.",
+                found, expected
+            ),
+            ReorderError::IndexOutOfBounds(index) => {
+                write!(f, "There is no slot at index {}.", index)
+            }
+            ReorderError::DuplicateIndex(index) => {
+                write!(f, "The slot at index {} is used more than once.", index)
+            }
+            ReorderError::ItemLost(index) => write!(
+                f,
+                "The item in the slot at index {} is not in the new order.",
+                index
+            ),
+        }
+    }
+}
+
+impl Error for ReorderError {}
+
+/// Returned when an inventory cannot be read back by an `InventorySerializer`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DeserializeError(pub String);
+
+impl Display for DeserializeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Could not deserialize the inventory: {}", self.0)
+    }
+}
+
+impl Error for DeserializeError {}
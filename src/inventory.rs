@@ -0,0 +1,1011 @@
+//! Inventory types that can be used in place of a `Vec<Slot>`.
+//!
+//! These deref to a slice of slots, so the functions in `inventory_management` work with them.
+use std::cmp::Ordering;
+use std::marker::PhantomData;
+use std::ops::{Deref, DerefMut, Index, IndexMut};
+
+use crate::errors::{InventoryError, QuantityError};
+use crate::inventory_management::{
+    add_to_inventory, add_to_inventory_bounded, can_add_item_to_inventory, compare_item_instances,
+    empty_quant_in_inventory, inventory_contains_item, item_exists_in_inventory,
+    item_instance_weight, item_total_quantity, remove_from_inventory, sort_inventory_by,
+    validate_inventory, ItemSort,
+};
+use crate::traits::{Item, ItemInstance, ItemWeight, Slot};
+
+/// An inventory with a fixed number of slots, stored in an array instead of a `Vec`.
+///
+/// Good for small inventories of a known size, like a hotbar or equipment slots,
+/// as it does not need a heap allocation.
+///
+/// ```
+/// # use game_inventory::samples::{DefaultItem, DefaultItemInstance, DefaultSlot, TORCH_INST};
+/// # use game_inventory::inventory::FixedInventory;
+/// # use game_inventory::helpers::add_to_inventory;
+/// # use game_inventory::traits::Slot;
+/// let mut hotbar: FixedInventory<DefaultSlot<DefaultItem, DefaultItemInstance<DefaultItem>>, 9> =
+///     FixedInventory::empty();
+/// add_to_inventory(&mut hotbar, TORCH_INST.clone().unwrap());
+/// assert_eq!(hotbar[0].quantity(), 23);
+/// assert!(hotbar.iter().skip(1).all(|s| s.item_instance().is_none()));
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct FixedInventory<S, const N: usize>(pub [S; N]);
+
+impl<S, const N: usize> FixedInventory<S, N> {
+    /// Creates an inventory from an array of slots.
+    pub fn new(slots: [S; N]) -> Self {
+        FixedInventory(slots)
+    }
+
+    /// Creates an inventory where every slot is empty.
+    pub fn empty<Id: Eq, I: Item<Id = Id>, II: ItemInstance<I>>() -> Self
+    where
+        S: Slot<I, II>,
+    {
+        FixedInventory(std::array::from_fn(|_| S::new(None)))
+    }
+
+    /// Iterates over the slots in the inventory.
+    pub fn iter(&self) -> std::slice::Iter<'_, S> {
+        self.0.iter()
+    }
+
+    /// Iterates mutably over the slots in the inventory.
+    pub fn iter_mut(&mut self) -> std::slice::IterMut<'_, S> {
+        self.0.iter_mut()
+    }
+}
+
+impl<S, const N: usize> Index<usize> for FixedInventory<S, N> {
+    type Output = S;
+
+    fn index(&self, index: usize) -> &S {
+        &self.0[index]
+    }
+}
+
+impl<S, const N: usize> IndexMut<usize> for FixedInventory<S, N> {
+    fn index_mut(&mut self, index: usize) -> &mut S {
+        &mut self.0[index]
+    }
+}
+
+impl<S, const N: usize> Deref for FixedInventory<S, N> {
+    type Target = [S];
+
+    fn deref(&self) -> &[S] {
+        &self.0
+    }
+}
+
+impl<S, const N: usize> DerefMut for FixedInventory<S, N> {
+    fn deref_mut(&mut self) -> &mut [S] {
+        &mut self.0
+    }
+}
+
+impl<S, const N: usize> AsRef<[S]> for FixedInventory<S, N> {
+    fn as_ref(&self) -> &[S] {
+        &self.0
+    }
+}
+
+impl<S, const N: usize> AsMut<[S]> for FixedInventory<S, N> {
+    fn as_mut(&mut self) -> &mut [S] {
+        &mut self.0
+    }
+}
+
+/// A `Vec<Slot>` with the functions from `inventory_management` as methods.
+///
+/// Derefs to `Vec<S>`, so it can be used anywhere a `Vec<Slot>` is expected.
+/// The free functions are still there for generic code, this only makes them easier to find.
+///
+/// ```
+/// # use game_inventory::samples::{DefaultItem, DefaultItemInstance, DefaultSlot, TORCH, TORCH_INST, SWORD_INST};
+/// # use game_inventory::inventory::Inventory;
+/// # use game_inventory::traits::{Item, ItemInstance};
+/// # use std::sync::Arc;
+/// let mut inventory: Inventory<DefaultSlot<DefaultItem, DefaultItemInstance<DefaultItem>>> =
+///     Inventory::empty(2);
+/// inventory.add_item(TORCH_INST.clone().unwrap());
+/// inventory.add_item(SWORD_INST.clone().unwrap());
+/// assert!(inventory.is_full());
+/// assert_eq!(inventory.count_item(TORCH.id()), 23);
+/// inventory.remove_item(DefaultItemInstance::new(Arc::new(TORCH.clone()), 23).unwrap());
+/// assert!(!inventory.contains_item_type(TORCH.id()));
+/// assert_eq!(inventory.len(), 2);
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Inventory<S>(pub Vec<S>);
+
+impl<S> Inventory<S> {
+    /// Creates an inventory from a `Vec` of slots.
+    pub fn new(slots: Vec<S>) -> Self {
+        Inventory(slots)
+    }
+
+    /// Creates an inventory with `size` empty slots.
+    pub fn empty<Id: Eq, I: Item<Id = Id>, II: ItemInstance<I>>(size: usize) -> Self
+    where
+        S: Slot<I, II>,
+    {
+        Inventory((0..size).map(|_| S::new(None)).collect())
+    }
+
+    /// See `add_to_inventory`.
+    pub fn add_item<Id: Eq, I: Item<Id = Id>, II: ItemInstance<I> + Clone>(
+        &mut self,
+        other: II,
+    ) -> Option<II>
+    where
+        S: Slot<I, II>,
+    {
+        add_to_inventory(&mut self.0, other)
+    }
+
+    /// See `remove_from_inventory`.
+    pub fn remove_item<Id: Eq, I: Item<Id = Id>, II: ItemInstance<I>>(
+        &mut self,
+        other: II,
+    ) -> Option<II>
+    where
+        S: Slot<I, II>,
+    {
+        remove_from_inventory(&mut self.0, other)
+    }
+
+    /// See `can_add_item_to_inventory`.
+    pub fn can_add_item<Id: Eq, I: Item<Id = Id>, II: ItemInstance<I>>(&self, other: II) -> bool
+    where
+        S: Slot<I, II>,
+    {
+        can_add_item_to_inventory(&self.0, other)
+    }
+
+    /// See `inventory_contains_item`.
+    pub fn contains_item<Id: Eq, I: Item<Id = Id>, II: ItemInstance<I>>(&self, other: II) -> bool
+    where
+        S: Slot<I, II>,
+    {
+        inventory_contains_item(&self.0, other)
+    }
+
+    /// See `item_exists_in_inventory`.
+    pub fn contains_item_type<Id: Eq, I: Item<Id = Id>, II: ItemInstance<I>>(&self, id: Id) -> bool
+    where
+        S: Slot<I, II>,
+    {
+        item_exists_in_inventory(&self.0, id)
+    }
+
+    /// See `item_total_quantity`.
+    pub fn count_item<Id: Eq, I: Item<Id = Id>, II: ItemInstance<I>>(&self, id: Id) -> u32
+    where
+        S: Slot<I, II>,
+    {
+        item_total_quantity(&self.0, id)
+    }
+
+    /// See `empty_quant_in_inventory`.
+    pub fn empty_slots<Id: Eq, I: Item<Id = Id>, II: ItemInstance<I>>(&self) -> usize
+    where
+        S: Slot<I, II>,
+    {
+        empty_quant_in_inventory(&self.0)
+    }
+
+    /// Whether every slot in the inventory holds an item.
+    pub fn is_full<Id: Eq, I: Item<Id = Id>, II: ItemInstance<I>>(&self) -> bool
+    where
+        S: Slot<I, II>,
+    {
+        !self.can_add_slot()
+    }
+
+    /// The number of empty slots left in the inventory, for showing in a HUD.
+    ///
+    /// ```
+    /// # use game_inventory::samples::{DefaultSlot, TORCH_INST};
+    /// # use game_inventory::inventory::Inventory;
+    /// # use game_inventory::traits::Slot;
+    /// let inventory = Inventory::new(vec![DefaultSlot::new(TORCH_INST.clone()), DefaultSlot::new(None)]);
+    /// assert_eq!(inventory.slots_remaining(), 1);
+    /// assert!(inventory.can_add_slot());
+    /// ```
+    #[inline]
+    pub fn slots_remaining<Id: Eq, I: Item<Id = Id>, II: ItemInstance<I>>(&self) -> usize
+    where
+        S: Slot<I, II>,
+    {
+        self.empty_slots()
+    }
+
+    /// Whether there is at least one empty slot left in the inventory.
+    ///
+    /// Stops at the first empty slot, so it is cheaper than `slots_remaining` when
+    /// you only need to know if there is room.
+    #[inline]
+    pub fn can_add_slot<Id: Eq, I: Item<Id = Id>, II: ItemInstance<I>>(&self) -> bool
+    where
+        S: Slot<I, II>,
+    {
+        self.0.iter().any(|s| s.is_empty())
+    }
+}
+
+impl<S> Deref for Inventory<S> {
+    type Target = Vec<S>;
+
+    fn deref(&self) -> &Vec<S> {
+        &self.0
+    }
+}
+
+impl<S> DerefMut for Inventory<S> {
+    fn deref_mut(&mut self) -> &mut Vec<S> {
+        &mut self.0
+    }
+}
+
+impl<S> From<Vec<S>> for Inventory<S> {
+    fn from(slots: Vec<S>) -> Self {
+        Inventory(slots)
+    }
+}
+
+/// An inventory that sorts itself every time its items are changed.
+///
+/// Reading the inventory never sorts it. While adding lots of items at once,
+/// call `disable_auto_sort` and then `enable_auto_sort` to only sort once at the end.
+///
+/// ```
+/// # use game_inventory::samples::{DefaultSlot, DefaultItemInstance, TORCH, TORCH_INST, JUNK_INST, SWORD_INST};
+/// # use game_inventory::inventory::AutoSortInventory;
+/// # use game_inventory::helpers::ItemSort;
+/// # use game_inventory::traits::{Slot, ItemInstance};
+/// # use std::sync::Arc;
+/// let mut inventory = AutoSortInventory::new(vec![
+///     DefaultSlot::new(None),
+///     DefaultSlot::new(TORCH_INST.clone()),
+///     DefaultSlot::new(None),
+/// ])
+/// .with_strategy(ItemSort::QuantityDescending);
+/// inventory.add_item(JUNK_INST.clone().unwrap());
+/// assert_eq!(inventory[0].quantity(), 91);
+/// assert_eq!(inventory[1].quantity(), 23);
+///
+/// inventory.disable_auto_sort();
+/// inventory.remove_item(DefaultItemInstance::new(Arc::new(TORCH.clone()), 23).unwrap());
+/// inventory.add_item(SWORD_INST.clone().unwrap());
+/// assert!(inventory[2].item_instance().is_none());
+/// inventory.enable_auto_sort();
+/// assert!(inventory[1].item_instance().is_some());
+/// ```
+#[derive(Debug, Clone)]
+pub struct AutoSortInventory<S> {
+    inner: Vec<S>,
+    sort_strategy: ItemSort,
+    auto_sort: bool,
+}
+
+impl<S> AutoSortInventory<S> {
+    /// Creates an inventory that sorts by `ItemSort::Id`.
+    ///
+    /// The slots are not sorted until the inventory is changed.
+    pub fn new(slots: Vec<S>) -> Self {
+        AutoSortInventory {
+            inner: slots,
+            sort_strategy: ItemSort::Id,
+            auto_sort: true,
+        }
+    }
+
+    /// Sets the order the inventory is sorted in.
+    pub fn with_strategy(mut self, sort_strategy: ItemSort) -> Self {
+        self.sort_strategy = sort_strategy;
+        self
+    }
+
+    /// Stops the inventory from being sorted until `enable_auto_sort` is called.
+    pub fn disable_auto_sort(&mut self) {
+        self.auto_sort = false;
+    }
+
+    /// Starts sorting the inventory again, and sorts it.
+    pub fn enable_auto_sort<Id: Ord, I: Item<Id = Id>, II: ItemInstance<I>>(&mut self)
+    where
+        S: Slot<I, II>,
+    {
+        self.auto_sort = true;
+        self.sort();
+    }
+
+    /// Sorts the inventory, even if auto sorting is disabled.
+    pub fn sort<Id: Ord, I: Item<Id = Id>, II: ItemInstance<I>>(&mut self)
+    where
+        S: Slot<I, II>,
+    {
+        sort_inventory_by(&mut self.inner, self.sort_strategy);
+    }
+
+    /// See `add_to_inventory`.
+    pub fn add_item<Id: Ord, I: Item<Id = Id>, II: ItemInstance<I> + Clone>(
+        &mut self,
+        other: II,
+    ) -> Option<II>
+    where
+        S: Slot<I, II>,
+    {
+        let res = add_to_inventory(&mut self.inner, other);
+        self.sort_if_enabled();
+        res
+    }
+
+    /// See `remove_from_inventory`.
+    pub fn remove_item<Id: Ord, I: Item<Id = Id>, II: ItemInstance<I>>(
+        &mut self,
+        other: II,
+    ) -> Option<II>
+    where
+        S: Slot<I, II>,
+    {
+        let res = remove_from_inventory(&mut self.inner, other);
+        self.sort_if_enabled();
+        res
+    }
+
+    /// Gets the slots out of the inventory.
+    pub fn into_inner(self) -> Vec<S> {
+        self.inner
+    }
+
+    fn sort_if_enabled<Id: Ord, I: Item<Id = Id>, II: ItemInstance<I>>(&mut self)
+    where
+        S: Slot<I, II>,
+    {
+        if self.auto_sort {
+            self.sort();
+        }
+    }
+}
+
+impl<S> Deref for AutoSortInventory<S> {
+    type Target = Vec<S>;
+
+    fn deref(&self) -> &Vec<S> {
+        &self.inner
+    }
+}
+
+/// An inventory that keeps its items in order by putting each new stack where it belongs.
+///
+/// Only occupied slots are kept, and a new slot is made for every stack that does not fit
+/// into an existing one. Unlike `AutoSortInventory` the whole inventory is never re-sorted,
+/// each changed slot is moved to its place with a binary search.
+/// Stacks that compare equal stay in the order they were added.
+///
+/// ```
+/// # use game_inventory::samples::{DefaultSlot, DefaultItemInstance, TORCH, TORCH_INST, JUNK_INST, SWORD_INST};
+/// # use game_inventory::inventory::SortedInsertInventory;
+/// # use game_inventory::helpers::ItemSort;
+/// # use game_inventory::traits::{Slot, ItemInstance};
+/// # use std::sync::Arc;
+/// let mut inventory: SortedInsertInventory<DefaultSlot<_, _>> = SortedInsertInventory::new(ItemSort::Id);
+/// inventory.add_item(TORCH_INST.clone().unwrap());
+/// inventory.add_item(SWORD_INST.clone().unwrap());
+/// inventory.add_item(JUNK_INST.clone().unwrap());
+/// inventory.add_item(DefaultItemInstance::new(Arc::new(TORCH.clone()), 90).unwrap());
+/// let ids: Vec<_> = inventory.iter().map(|s| (s.item_id().unwrap(), s.quantity())).collect();
+/// assert_eq!(ids, vec![("junk", 91), ("sword", 0), ("torch", 100), ("torch", 13)]);
+///
+/// inventory.remove_item(DefaultItemInstance::new(Arc::new(TORCH.clone()), 100).unwrap());
+/// assert_eq!(inventory.len(), 3);
+/// assert_eq!(inventory[2].quantity(), 13);
+/// ```
+#[derive(Debug, Clone)]
+pub struct SortedInsertInventory<S> {
+    inner: Vec<S>,
+    sort_key: ItemSort,
+}
+
+impl<S> SortedInsertInventory<S> {
+    /// Creates an empty inventory sorted by `sort_key`.
+    pub fn new(sort_key: ItemSort) -> Self {
+        SortedInsertInventory {
+            inner: Vec::new(),
+            sort_key,
+        }
+    }
+
+    /// Creates an inventory from existing slots, dropping the empty ones and sorting the rest.
+    pub fn from_slots<Id: Ord, I: Item<Id = Id>, II: ItemInstance<I>>(
+        slots: Vec<S>,
+        sort_key: ItemSort,
+    ) -> Self
+    where
+        S: Slot<I, II>,
+    {
+        let mut inventory = SortedInsertInventory::new(sort_key);
+        for slot in slots {
+            inventory.insert_sorted(slot);
+        }
+        inventory
+    }
+
+    /// The order the inventory is kept in.
+    pub fn sort_key(&self) -> ItemSort {
+        self.sort_key
+    }
+
+    /// Adds an item, topping up existing stacks first and then making new slots for the rest.
+    ///
+    /// Returns the item if it cannot be put into a slot at all.
+    pub fn add_item<Id: Ord, I: Item<Id = Id>, II: ItemInstance<I> + Clone>(
+        &mut self,
+        other: II,
+    ) -> Option<II>
+    where
+        S: Slot<I, II>,
+    {
+        let before = self.quantities();
+        let mut res = add_to_inventory(&mut self.inner, other);
+        self.resettle(before);
+        while let Some(remaining) = res {
+            let mut slot = S::new(None);
+            res = add_to_inventory(std::slice::from_mut(&mut slot), remaining.clone());
+            if res.as_ref().map(|r| r.quant()) == Some(remaining.quant()) {
+                return res;
+            }
+            self.insert_sorted(slot);
+        }
+        None
+    }
+
+    /// See `remove_from_inventory`. Slots that are emptied are removed.
+    pub fn remove_item<Id: Ord, I: Item<Id = Id>, II: ItemInstance<I>>(
+        &mut self,
+        other: II,
+    ) -> Option<II>
+    where
+        S: Slot<I, II>,
+    {
+        let before = self.quantities();
+        let res = remove_from_inventory(&mut self.inner, other);
+        self.resettle(before);
+        res
+    }
+
+    /// Gets the slots out of the inventory.
+    pub fn into_inner(self) -> Vec<S> {
+        self.inner
+    }
+
+    fn quantities<Id: Ord, I: Item<Id = Id>, II: ItemInstance<I>>(&self) -> Vec<u16>
+    where
+        S: Slot<I, II>,
+    {
+        self.inner.iter().map(|s| s.quantity()).collect()
+    }
+
+    /// Takes out every slot that changed since `before` and puts it back in its place,
+    /// dropping the ones that were emptied.
+    ///
+    /// Slots are put back before the stacks they compare equal to that came after them,
+    /// so topping up a stack does not move it behind its equals.
+    fn resettle<Id: Ord, I: Item<Id = Id>, II: ItemInstance<I>>(&mut self, before: Vec<u16>)
+    where
+        S: Slot<I, II>,
+    {
+        let mut settled = Vec::with_capacity(self.inner.len());
+        let mut changed = Vec::new();
+        for (index, slot) in std::mem::take(&mut self.inner).into_iter().enumerate() {
+            if slot.is_empty() {
+                continue;
+            }
+            match slot.quantity() == before[index] {
+                true => settled.push((index, slot)),
+                false => changed.push((index, slot)),
+            }
+        }
+        for (index, slot) in changed {
+            let item_instance = match slot.item_instance() {
+                None => continue,
+                Some(i) => i,
+            };
+            let at = settled.partition_point(|(other_index, other)| match other.item_instance() {
+                None => false,
+                Some(other) => {
+                    match compare_item_instances(self.sort_key, &other, &item_instance) {
+                        Ordering::Less => true,
+                        Ordering::Equal => *other_index < index,
+                        Ordering::Greater => false,
+                    }
+                }
+            });
+            settled.insert(at, (index, slot));
+        }
+        self.inner = settled.into_iter().map(|(_, slot)| slot).collect();
+    }
+
+    fn insert_sorted<Id: Ord, I: Item<Id = Id>, II: ItemInstance<I>>(&mut self, slot: S)
+    where
+        S: Slot<I, II>,
+    {
+        let item_instance = match slot.item_instance() {
+            None => return,
+            Some(i) => i,
+        };
+        let index = self.inner.partition_point(|s| match s.item_instance() {
+            None => false,
+            Some(other) => {
+                compare_item_instances(self.sort_key, &other, &item_instance) != Ordering::Greater
+            }
+        });
+        self.inner.insert(index, slot);
+    }
+}
+
+impl<S> Deref for SortedInsertInventory<S> {
+    type Target = Vec<S>;
+
+    fn deref(&self) -> &Vec<S> {
+        &self.inner
+    }
+}
+
+/// Marks what an inventory belongs to, see `TypedInventory`.
+pub trait InventoryKind {}
+
+/// The inventory carried by the player.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Player;
+
+/// The inventory of a chest or other storage container.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Container;
+
+impl InventoryKind for Player {}
+impl InventoryKind for Container {}
+
+/// A `Vec<Slot>` tagged with what it belongs to, so a chest cannot be passed where the
+/// players inventory is expected.
+///
+/// Functions that work for any inventory are on `TypedInventory<S, K>`,
+/// while things like the hotbar only exist on `TypedInventory<S, Player>`.
+/// ```
+/// # use game_inventory::samples::{DefaultItem, DefaultItemInstance, DefaultSlot, TORCH_INST};
+/// # use game_inventory::inventory::{TypedInventory, Player, Container};
+/// # use game_inventory::traits::Slot;
+/// type TestSlot<'a> = DefaultSlot<'a, DefaultItem<'a>, DefaultItemInstance<DefaultItem<'a>>>;
+/// let mut chest: TypedInventory<TestSlot, Container> =
+///     TypedInventory::new(vec![DefaultSlot::new(TORCH_INST.clone())]);
+/// let mut player: TypedInventory<TestSlot, Player> = TypedInventory::empty(4);
+/// assert!(chest.transfer_to(&mut player, 0).unwrap().is_none());
+/// assert_eq!(player.hotbar(2)[0].quantity(), 23);
+/// assert!(chest[0].item_instance().is_none());
+/// ```
+/// A container has no hotbar.
+/// ```compile_fail
+/// # use game_inventory::samples::{DefaultItem, DefaultItemInstance, DefaultSlot};
+/// # use game_inventory::inventory::{TypedInventory, Container};
+/// let chest: TypedInventory<DefaultSlot<DefaultItem, DefaultItemInstance<DefaultItem>>, Container> =
+///     TypedInventory::empty(4);
+/// chest.hotbar(2);
+/// ```
+#[derive(Debug, Clone)]
+pub struct TypedInventory<S, K: InventoryKind>(pub Vec<S>, PhantomData<K>);
+
+impl<S, K: InventoryKind> TypedInventory<S, K> {
+    /// Creates an inventory from a `Vec` of slots.
+    pub fn new(slots: Vec<S>) -> Self {
+        TypedInventory(slots, PhantomData)
+    }
+
+    /// Creates an inventory with `size` empty slots.
+    pub fn empty<Id: Eq, I: Item<Id = Id>, II: ItemInstance<I>>(size: usize) -> Self
+    where
+        S: Slot<I, II>,
+    {
+        Self::new((0..size).map(|_| S::new(None)).collect())
+    }
+
+    /// Moves the item in the slot at `index` into another inventory with `add_to_inventory`.
+    ///
+    /// Whatever does not fit stays in the slot and is returned.
+    pub fn transfer_to<Id: Eq, I: Item<Id = Id>, II: ItemInstance<I> + Clone, K2: InventoryKind>(
+        &mut self,
+        other: &mut TypedInventory<S, K2>,
+        index: impl Into<InventoryIndex>,
+    ) -> Result<Option<II>, InventoryError>
+    where
+        S: Slot<I, II>,
+    {
+        let index = index.into();
+        let slot = index
+            .checked_get_mut(&mut self.0)
+            .ok_or(InventoryError::IndexOutOfBounds(index.get()))?;
+        let item_instance = slot.item_instance().ok_or(InventoryError::SlotEmpty)?;
+        let res = add_to_inventory(&mut other.0, item_instance);
+        slot.set_item_instance(&res);
+        Ok(res)
+    }
+
+    /// Gets the slots out of the inventory.
+    pub fn into_inner(self) -> Vec<S> {
+        self.0
+    }
+}
+
+impl<S> TypedInventory<S, Player> {
+    /// The first `len` slots of the inventory, which make up the hotbar.
+    pub fn hotbar(&self, len: usize) -> &[S] {
+        &self.0[..len.min(self.0.len())]
+    }
+
+    /// The first `len` slots of the inventory, which make up the hotbar.
+    pub fn hotbar_mut(&mut self, len: usize) -> &mut [S] {
+        let len = len.min(self.0.len());
+        &mut self.0[..len]
+    }
+}
+
+impl<S, K: InventoryKind> Deref for TypedInventory<S, K> {
+    type Target = Vec<S>;
+
+    fn deref(&self) -> &Vec<S> {
+        &self.0
+    }
+}
+
+impl<S, K: InventoryKind> DerefMut for TypedInventory<S, K> {
+    fn deref_mut(&mut self) -> &mut Vec<S> {
+        &mut self.0
+    }
+}
+
+/// The rules a `ConstrainedInventory` enforces.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct InventoryConstraints {
+    /// The most slots the inventory can grow to, or `None` for no limit.
+    pub max_slots: Option<usize>,
+    /// The most the items in the inventory can weigh together, or `None` for no limit.
+    ///
+    /// Only checked by inventories made with `ConstrainedInventory::with_weights`,
+    /// since the weight of an item comes from `ItemWeight`.
+    pub max_weight: Option<f32>,
+    /// Whether to refuse putting an item in a slot that does not `accept` it.
+    ///
+    /// `add_item` never picks a slot that does not accept the item either way,
+    /// this also covers `set_item`, `push_slot` and validation.
+    pub enforce_slot_filters: bool,
+    /// Whether to run `validate_inventory` before every change.
+    ///
+    /// Only done in debug builds, so it can be left on for release.
+    pub validate_on_mutate: bool,
+}
+
+/// An inventory that grows as items are added to it, within a set of `InventoryConstraints`.
+///
+/// Only derefs to `Vec<S>` for reading, all changes go through its methods so the
+/// constraints cannot be skipped. Every change is checked before anything is changed,
+/// so a change that returns an error leaves the inventory as it was.
+/// ```
+/// # use game_inventory::samples::{DefaultSlot, TORCH_INST, SWORD_INST};
+/// # use game_inventory::inventory::{ConstrainedInventory, InventoryConstraints};
+/// # use game_inventory::errors::InventoryError;
+/// # use game_inventory::traits::Slot;
+/// let mut inventory = ConstrainedInventory::new(
+///     vec![DefaultSlot::new(TORCH_INST.clone())],
+///     InventoryConstraints { max_slots: Some(2), validate_on_mutate: true, ..Default::default() },
+/// );
+/// assert!(inventory.add_item(SWORD_INST.clone().unwrap()).unwrap().is_none());
+/// assert_eq!(inventory.len(), 2);
+/// assert_eq!(inventory.add_item(SWORD_INST.clone().unwrap()).unwrap_err(), InventoryError::InventoryFull);
+/// assert_eq!(inventory.push_slot(DefaultSlot::new(None)), Err(InventoryError::InventoryFull));
+/// ```
+#[derive(Debug, Clone)]
+pub struct ConstrainedInventory<S> {
+    inner: Vec<S>,
+    constraints: InventoryConstraints,
+    weigh: Option<fn(&S) -> f32>,
+}
+
+impl<S> ConstrainedInventory<S> {
+    /// Creates an inventory from a `Vec` of slots, for items that do not have a weight.
+    ///
+    /// Panics in debug builds if `constraints` has a `max_weight`, use `with_weights` for that.
+    pub fn new(slots: Vec<S>, constraints: InventoryConstraints) -> Self {
+        debug_assert!(
+            constraints.max_weight.is_none(),
+            "max_weight needs ConstrainedInventory::with_weights"
+        );
+        ConstrainedInventory {
+            inner: slots,
+            constraints,
+            weigh: None,
+        }
+    }
+
+    /// Creates an inventory from a `Vec` of slots that also enforces `max_weight`.
+    pub fn with_weights<Id: Eq, I: Item<Id = Id> + ItemWeight, II: ItemInstance<I>>(
+        slots: Vec<S>,
+        constraints: InventoryConstraints,
+    ) -> Self
+    where
+        S: Slot<I, II>,
+    {
+        ConstrainedInventory {
+            inner: slots,
+            constraints,
+            weigh: Some(slot_weight::<I, II, S>),
+        }
+    }
+
+    /// The rules this inventory enforces.
+    pub fn constraints(&self) -> InventoryConstraints {
+        self.constraints
+    }
+
+    /// Adds an item, adding a slot for it if there is room, see `add_to_inventory_bounded`.
+    ///
+    /// Returns `InventoryError::TooHeavy` without adding anything if all of `other`
+    /// would go over `max_weight`.
+    /// ```
+    /// # use game_inventory::samples::{DefaultSlot, DefaultItem, DefaultItemInstance};
+    /// # use game_inventory::inventory::{ConstrainedInventory, InventoryConstraints};
+    /// # use game_inventory::errors::InventoryError;
+    /// # use game_inventory::traits::Slot;
+    /// # use std::sync::Arc;
+    /// let stone = Arc::new(DefaultItem::new("stone", 100, "junk").with_weight(2.0));
+    /// let mut inventory: ConstrainedInventory<DefaultSlot<_, _>> = ConstrainedInventory::with_weights(
+    ///     vec![DefaultSlot::new(None)],
+    ///     InventoryConstraints { max_weight: Some(30.0), ..Default::default() },
+    /// );
+    /// assert!(inventory.add_item(DefaultItemInstance::new(stone.clone(), 10).unwrap()).unwrap().is_none());
+    /// let too_many = DefaultItemInstance::new(stone, 6).unwrap();
+    /// assert_eq!(inventory.add_item(too_many), Err(InventoryError::TooHeavy));
+    /// assert_eq!(inventory[0].quantity(), 10);
+    /// ```
+    pub fn add_item<Id: Eq, I: Item<Id = Id>, II: ItemInstance<I> + Clone>(
+        &mut self,
+        other: II,
+    ) -> Result<Option<II>, InventoryError>
+    where
+        S: Slot<I, II>,
+    {
+        self.validate_if_enabled()?;
+        self.check_weight(0.0, self.weight_of(&other))?;
+        let max_slots = self.constraints.max_slots.unwrap_or(usize::MAX);
+        add_to_inventory_bounded(&mut self.inner, other, max_slots)
+    }
+
+    /// See `remove_from_inventory`.
+    pub fn remove_item<Id: Eq, I: Item<Id = Id>, II: ItemInstance<I>>(
+        &mut self,
+        other: II,
+    ) -> Result<Option<II>, InventoryError>
+    where
+        S: Slot<I, II>,
+    {
+        self.validate_if_enabled()?;
+        Ok(remove_from_inventory(&mut self.inner, other))
+    }
+
+    /// Sets the item instance of the slot at `index`, returning the one that was there.
+    ///
+    /// The slot is left as it was if the item does not fit in it.
+    /// ```
+    /// # use game_inventory::samples::{DefaultSlot, DefaultItem, DefaultItemInstance, TORCH, TORCH_INST, SWORD_INST};
+    /// # use game_inventory::inventory::{ConstrainedInventory, InventoryConstraints};
+    /// # use game_inventory::errors::InventoryError;
+    /// # use game_inventory::traits::{Slot, Item, ItemInstance};
+    /// # use std::sync::Arc;
+    /// let mut slot: DefaultSlot<DefaultItem, DefaultItemInstance<DefaultItem>> = DefaultSlot::new(None);
+    /// slot.item_filter = Some(|item| item.stackable());
+    /// let mut inventory = ConstrainedInventory::new(
+    ///     vec![slot],
+    ///     InventoryConstraints { enforce_slot_filters: true, ..Default::default() },
+    /// );
+    /// assert!(inventory.set_item(0, TORCH_INST.clone()).unwrap().is_none());
+    /// let too_many = DefaultItemInstance::new_unchecked(Arc::new(TORCH.clone()), 101);
+    /// assert!(inventory.set_item(0, Some(too_many)).is_err());
+    /// assert_eq!(inventory.set_item(0, SWORD_INST.clone()), Err(InventoryError::ItemNotAccepted));
+    /// assert_eq!(inventory[0].quantity(), 23);
+    /// ```
+    pub fn set_item<Id: Eq, I: Item<Id = Id>, II: ItemInstance<I> + Clone>(
+        &mut self,
+        index: impl Into<InventoryIndex>,
+        item_instance: Option<II>,
+    ) -> Result<Option<II>, InventoryError>
+    where
+        S: Slot<I, II>,
+    {
+        self.validate_if_enabled()?;
+        let index = index.into();
+        let slot = index
+            .checked_get(&self.inner)
+            .ok_or(InventoryError::IndexOutOfBounds(index.get()))?;
+        if let Some(ii) = &item_instance {
+            self.check_accepts(slot, ii)?;
+            if ii.item().stackable() && ii.quant() > slot.capacity_for(&ii.item()) {
+                return Err(QuantityError::ExceedsMaxQuantity {
+                    quantity: ii.quant(),
+                    max_quant: slot.capacity_for(&ii.item()),
+                }
+                .into());
+            }
+        }
+        let removed = self.weigh.map_or(0.0, |weigh| weigh(slot));
+        let added = item_instance.as_ref().map_or(0.0, |ii| self.weight_of(ii));
+        self.check_weight(removed, added)?;
+        let slot = &mut self.inner[index.get()];
+        let old = slot.item_instance();
+        slot.set_item_instance(&item_instance);
+        Ok(old)
+    }
+
+    /// Adds a slot to the end of the inventory.
+    ///
+    /// Returns `InventoryError::InventoryFull` if the inventory is at `max_slots`.
+    pub fn push_slot<Id: Eq, I: Item<Id = Id>, II: ItemInstance<I>>(
+        &mut self,
+        slot: S,
+    ) -> Result<(), InventoryError>
+    where
+        S: Slot<I, II>,
+    {
+        self.validate_if_enabled()?;
+        if let Some(max_slots) = self.constraints.max_slots {
+            if self.inner.len() >= max_slots {
+                return Err(InventoryError::InventoryFull);
+            }
+        }
+        if let Some(ii) = slot.item_instance() {
+            self.check_accepts(&slot, &ii)?;
+        }
+        self.check_weight(0.0, self.weigh.map_or(0.0, |weigh| weigh(&slot)))?;
+        validate_inventory(std::slice::from_ref(&slot))?;
+        self.inner.push(slot);
+        Ok(())
+    }
+
+    /// Gets the slots out of the inventory.
+    pub fn into_inner(self) -> Vec<S> {
+        self.inner
+    }
+
+    fn check_accepts<Id: Eq, I: Item<Id = Id>, II: ItemInstance<I>>(
+        &self,
+        slot: &S,
+        item_instance: &II,
+    ) -> Result<(), InventoryError>
+    where
+        S: Slot<I, II>,
+    {
+        if self.constraints.enforce_slot_filters && !slot.accepts(&item_instance.item()) {
+            return Err(InventoryError::ItemNotAccepted);
+        }
+        Ok(())
+    }
+
+    /// The weight of `item_instance`, or `0.0` if this inventory does not weigh its items.
+    fn weight_of<Id: Eq, I: Item<Id = Id>, II: ItemInstance<I> + Clone>(
+        &self,
+        item_instance: &II,
+    ) -> f32
+    where
+        S: Slot<I, II>,
+    {
+        match self.weigh {
+            Some(weigh) => weigh(&S::new(Some(item_instance.clone()))),
+            None => 0.0,
+        }
+    }
+
+    /// Checks that taking `removed` weight out of the inventory and putting `added` in
+    /// stays within `max_weight`.
+    fn check_weight(&self, removed: f32, added: f32) -> Result<(), InventoryError> {
+        let (max_weight, weigh) = match (self.constraints.max_weight, self.weigh) {
+            (Some(max_weight), Some(weigh)) => (max_weight, weigh),
+            _ => return Ok(()),
+        };
+        let current: f32 = self.inner.iter().map(weigh).sum();
+        match current - removed + added > max_weight {
+            true => Err(InventoryError::TooHeavy),
+            false => Ok(()),
+        }
+    }
+
+    fn validate_if_enabled<Id: Eq, I: Item<Id = Id>, II: ItemInstance<I>>(
+        &self,
+    ) -> Result<(), InventoryError>
+    where
+        S: Slot<I, II>,
+    {
+        if !cfg!(debug_assertions) || !self.constraints.validate_on_mutate {
+            return Ok(());
+        }
+        validate_inventory(&self.inner)?;
+        for slot in &self.inner {
+            if let Some(ii) = slot.item_instance() {
+                self.check_accepts(slot, &ii)?;
+            }
+        }
+        self.check_weight(0.0, 0.0)
+    }
+}
+
+fn slot_weight<I: Item + ItemWeight, II: ItemInstance<I>, S: Slot<I, II>>(slot: &S) -> f32 {
+    slot.item_instance()
+        .map_or(0.0, |ii| item_instance_weight(&ii))
+}
+
+impl<S> Deref for ConstrainedInventory<S> {
+    type Target = Vec<S>;
+
+    fn deref(&self) -> &Vec<S> {
+        &self.inner
+    }
+}
+
+/// The index of a slot in an inventory.
+///
+/// Helpers that take a slot index accept anything that converts into this,
+/// so a plain `usize` still works.
+/// ```
+/// # use game_inventory::samples::{DefaultSlot, TORCH_INST};
+/// # use game_inventory::inventory::InventoryIndex;
+/// # use game_inventory::traits::Slot;
+/// let inventory = vec![DefaultSlot::new(TORCH_INST.clone())];
+/// let index = InventoryIndex::new(0);
+/// assert!(index.is_valid(&inventory));
+/// assert_eq!(index.checked_get(&inventory).unwrap().quantity(), 23);
+/// assert!(InventoryIndex::from(1).checked_get(&inventory).is_none());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct InventoryIndex(usize);
+
+impl InventoryIndex {
+    /// Creates an index pointing to the slot at `index`.
+    pub const fn new(index: usize) -> Self {
+        InventoryIndex(index)
+    }
+
+    /// The position of the slot in the inventory.
+    pub const fn get(self) -> usize {
+        self.0
+    }
+
+    /// Checks if the index points to a slot in the inventory.
+    pub fn is_valid<S>(self, inventory: &[S]) -> bool {
+        self.0 < inventory.len()
+    }
+
+    /// Gets the slot at this index, or `None` if it is out of bounds.
+    pub fn checked_get<S>(self, inventory: &[S]) -> Option<&S> {
+        inventory.get(self.0)
+    }
+
+    /// Gets the slot at this index mutably, or `None` if it is out of bounds.
+    pub fn checked_get_mut<S>(self, inventory: &mut [S]) -> Option<&mut S> {
+        inventory.get_mut(self.0)
+    }
+}
+
+impl From<usize> for InventoryIndex {
+    fn from(index: usize) -> Self {
+        InventoryIndex(index)
+    }
+}
+
+impl From<InventoryIndex> for usize {
+    fn from(index: InventoryIndex) -> Self {
+        index.0
+    }
+}
@@ -1,6 +1,16 @@
 //! A collection of generic functions that operate on a `Vec<Slot>` (A collection of slots, AKA an inventory).
-use crate::slot_management::{combine_stack, unwrap_items_res};
-use crate::traits::{Item, ItemInstance, Slot};
+use std::cmp::{Ordering, Reverse};
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::hash::{Hash, Hasher};
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+use crate::errors::{
+    ApplyError, BatchRemoveError, CraftError, DuplicateError, InventoryError, QuantityError,
+    ReorderError, ValidationError,
+};
+use crate::traits::{Craftable, Item, ItemCategory, ItemInstance, ItemValue, ItemWeight, Slot};
 
 /// Checks if a `Vec<Slot>` contains an item with a matching name and quantity.
 ///
@@ -18,7 +28,7 @@ use crate::traits::{Item, ItemInstance, Slot};
 /// assert!(!inventory_contains_item_type(&inventory, JUNK.id()));
 /// ```
 pub fn inventory_contains_item<Id: Eq, I: Item<Id = Id>, II: ItemInstance<I>, S: Slot<I, II>>(
-    inventory: &Vec<S>,
+    inventory: &[S],
     other: II,
 ) -> bool {
     inventory.iter().any(|s| match s.item_instance() {
@@ -45,19 +55,64 @@ pub fn inventory_contains_item<Id: Eq, I: Item<Id = Id>, II: ItemInstance<I>, S:
 /// assert!(inventory_contains_item(&inventory, SWORD_INST.clone().unwrap()));
 /// assert!(!inventory_contains_item(&inventory, TORCH_INST.clone().unwrap()));
 /// ```
+/// This is the same as `item_exists_in_inventory`, use that in new code.
 pub fn inventory_contains_item_type<
     Id: Eq,
     I: Item<Id = Id>,
     II: ItemInstance<I>,
     S: Slot<I, II>,
 >(
-    inventory: &Vec<S>,
+    inventory: &[S],
     id: Id,
 ) -> bool {
-    inventory.iter().any(|s| match s.item_instance() {
-        Some(i) => i.item().id() == id,
-        None => false,
-    })
+    item_exists_in_inventory(inventory, id)
+}
+
+/// Checks if any item in a `Vec<Slot>` matches the predicate, stopping at the first match.
+///
+/// ```
+/// # use game_inventory::samples::{DefaultSlot, TORCH_INST, SWORD_INST};
+/// # use game_inventory::traits::{Slot, ItemInstance, Item};
+/// # use game_inventory::helpers::any_item_matching;
+/// let inventory = vec![
+///     DefaultSlot::new(TORCH_INST.clone()),
+///     DefaultSlot::new(SWORD_INST.clone()),
+///     DefaultSlot::new(None),
+/// ];
+/// assert!(any_item_matching(&inventory, |i| !i.item().stackable()));
+/// assert!(!any_item_matching(&inventory, |i| i.quant() > 50));
+/// ```
+pub fn any_item_matching<
+    Id: Eq,
+    I: Item<Id = Id>,
+    II: ItemInstance<I>,
+    S: Slot<I, II>,
+    F: Fn(&II) -> bool,
+>(
+    inventory: &[S],
+    predicate: F,
+) -> bool {
+    inventory
+        .iter()
+        .any(|s| s.item_instance().is_some_and(|i| predicate(&i)))
+}
+
+/// Checks if a `Vec<Slot>` contains an item with a matching id, stopping at the first match.
+///
+/// Use this over `inventory_contains_item_type`, which calls it and is only kept for existing code.
+/// ```
+/// # use game_inventory::samples::{DefaultSlot, TORCH, JUNK, TORCH_INST};
+/// # use game_inventory::traits::{Slot, Item};
+/// # use game_inventory::helpers::item_exists_in_inventory;
+/// let inventory = vec![DefaultSlot::new(None), DefaultSlot::new(TORCH_INST.clone())];
+/// assert!(item_exists_in_inventory(&inventory, TORCH.id()));
+/// assert!(!item_exists_in_inventory(&inventory, JUNK.id()));
+/// ```
+pub fn item_exists_in_inventory<Id: Eq, I: Item<Id = Id>, II: ItemInstance<I>, S: Slot<I, II>>(
+    inventory: &[S],
+    id: Id,
+) -> bool {
+    any_item_matching(inventory, |i| i.item().id() == id)
 }
 
 /// Gets the total quantity of all items with a matching name.
@@ -84,28 +139,357 @@ pub fn inventory_contains_item_type<
 /// let inventory = vec![
 ///     DefaultSlot::new(TORCH_FULL_STACK_INST.clone()),
 ///     DefaultSlot::new(SWORD_INST.clone()),
-///     DefaultSlot::new(Some(DefaultItemInstance::new(Arc::new(SWORD.clone()), 123))),
+///     DefaultSlot::new(Some(DefaultItemInstance::new_unchecked(Arc::new(SWORD.clone()), 123))),
 /// ];
 /// assert_eq!(quant_in_inventory(&inventory, SWORD.id()), 2)
 /// ```
+/// Stops at `u16::MAX` instead of overflowing, use `item_total_quantity` for the full count.
+/// ```
+/// # use game_inventory::samples::{DefaultSlot, TORCH, TORCH_FULL_STACK_INST};
+/// # use game_inventory::traits::{Slot, Item};
+/// # use game_inventory::helpers::quant_in_inventory;
+/// let inventory: Vec<_> = (0..700).map(|_| DefaultSlot::new(TORCH_FULL_STACK_INST.clone())).collect();
+/// assert_eq!(quant_in_inventory(&inventory, TORCH.id()), u16::MAX);
+/// ```
 pub fn quant_in_inventory<Id: Eq, I: Item<Id = Id>, II: ItemInstance<I>, S: Slot<I, II>>(
-    inventory: &Vec<S>,
+    inventory: &[S],
     id: Id,
 ) -> u16 {
+    u16::try_from(item_total_quantity(inventory, id)).unwrap_or(u16::MAX)
+}
+
+/// Like `quant_in_inventory`, but adds up the quantities as a `u32`.
+///
+/// An inventory with many full stacks can hold more than `u16::MAX` of an item,
+/// which `quant_in_inventory` can only report as `u16::MAX`.
+/// ```
+/// # use game_inventory::samples::{DefaultSlot, TORCH, TORCH_FULL_STACK_INST, SWORD_INST};
+/// # use game_inventory::traits::{Slot, Item};
+/// # use game_inventory::helpers::item_total_quantity;
+/// let mut inventory: Vec<_> = (0..700).map(|_| DefaultSlot::new(TORCH_FULL_STACK_INST.clone())).collect();
+/// inventory.push(DefaultSlot::new(SWORD_INST.clone()));
+/// assert_eq!(item_total_quantity(&inventory, TORCH.id()), 70_000);
+/// assert_eq!(item_total_quantity(&inventory, "sword"), 1);
+/// ```
+pub fn item_total_quantity<Id: Eq, I: Item<Id = Id>, II: ItemInstance<I>, S: Slot<I, II>>(
+    inventory: &[S],
+    id: Id,
+) -> u32 {
+    inventory
+        .iter()
+        .filter_map(|slot| slot.item_instance())
+        .filter(|ii| ii.item().id() == id)
+        .map(|ii| match ii.item().stackable() {
+            true => ii.quant() as u32,
+            false => 1,
+        })
+        .sum()
+}
+
+/// The combined weight of every item in an inventory.
+///
+/// Stacks count their quantity, unstackable items count as one item.
+/// ```
+/// # use game_inventory::samples::{DefaultSlot, DefaultItem, DefaultItemInstance};
+/// # use game_inventory::traits::{Slot, ItemInstance};
+/// # use game_inventory::helpers::inventory_weight;
+/// # use std::sync::Arc;
+/// let stone = Arc::new(DefaultItem::new("stone", 100, "junk").with_weight(2.0));
+/// let anvil = Arc::new(DefaultItem::new("anvil", 0, "junk").with_weight(50.0));
+/// let inventory = vec![
+///     DefaultSlot::new(Some(DefaultItemInstance::new(stone, 10).unwrap())),
+///     DefaultSlot::new(Some(DefaultItemInstance::new(anvil, 0).unwrap())),
+///     DefaultSlot::new(None),
+/// ];
+/// assert_eq!(inventory_weight(&inventory), 70.0);
+/// ```
+pub fn inventory_weight<
+    Id: Eq,
+    I: Item<Id = Id> + ItemWeight,
+    II: ItemInstance<I>,
+    S: Slot<I, II>,
+>(
+    inventory: &[S],
+) -> f32 {
     inventory
         .iter()
-        .fold(0, |quant, slot| match slot.item_instance() {
+        .filter_map(|slot| slot.item_instance())
+        .map(|ii| item_instance_weight(&ii))
+        .sum()
+}
+
+pub(crate) fn item_instance_weight<I: Item + ItemWeight, II: ItemInstance<I>>(
+    item_instance: &II,
+) -> f32 {
+    let count = match item_instance.item().stackable() {
+        true => item_instance.quant(),
+        false => 1,
+    };
+    item_instance.item().weight() * count as f32
+}
+
+/// Iterates over the item instances in an inventory, skipping empty slots.
+///
+/// Nothing is collected, so this can be used every frame. Each instance is
+/// cloned out of its slot with `Slot::item_instance`.
+/// ```
+/// # use game_inventory::samples::{DefaultSlot, TORCH_INST, SWORD_INST};
+/// # use game_inventory::traits::{Slot, ItemInstance};
+/// # use game_inventory::helpers::as_item_instances;
+/// let inventory = vec![DefaultSlot::new(TORCH_INST.clone()), DefaultSlot::new(None), DefaultSlot::new(SWORD_INST.clone())];
+/// let quants: u16 = as_item_instances(&inventory).map(|i| i.quant()).sum();
+/// assert_eq!(quants, 23);
+/// assert_eq!(as_item_instances(&inventory).count(), 2);
+/// ```
+pub fn as_item_instances<'a, Id: Eq, I: Item<Id = Id>, II: ItemInstance<I>, S: Slot<I, II>>(
+    inventory: &'a [S],
+) -> impl Iterator<Item = II> + 'a {
+    inventory.iter().filter_map(|s| s.item_instance())
+}
+
+/// Collects the item instances in an inventory into a `Vec`, skipping empty slots.
+///
+/// See `as_item_instances` for doing this without collecting.
+/// ```
+/// # use game_inventory::samples::{DefaultSlot, TORCH_INST, SWORD_INST};
+/// # use game_inventory::traits::Slot;
+/// # use game_inventory::helpers::collect_item_instances;
+/// let inventory = vec![DefaultSlot::new(None), DefaultSlot::new(SWORD_INST.clone())];
+/// assert_eq!(collect_item_instances(&inventory), vec![SWORD_INST.clone().unwrap()]);
+/// ```
+pub fn collect_item_instances<Id: Eq, I: Item<Id = Id>, II: ItemInstance<I>, S: Slot<I, II>>(
+    inventory: &[S],
+) -> Vec<II> {
+    as_item_instances(inventory).collect()
+}
+
+/// Iterates over the slots of an inventory along with their index, optionally skipping empty slots.
+///
+/// Made with `iter_slots`.
+pub struct InventorySlotIterator<'a, I, II, S> {
+    inner: std::iter::Enumerate<std::slice::Iter<'a, S>>,
+    skip_empty: bool,
+    phantom: PhantomData<(I, II)>,
+}
+
+impl<'a, I: Item, II: ItemInstance<I>, S: Slot<I, II>> Iterator
+    for InventorySlotIterator<'a, I, II, S>
+{
+    type Item = (usize, &'a S);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let skip_empty = self.skip_empty;
+        self.inner.find(|(_, s)| !skip_empty || s.is_occupied())
+    }
+}
+
+/// The mutable version of `InventorySlotIterator`, made with `iter_slots_mut`.
+pub struct InventorySlotIteratorMut<'a, I, II, S> {
+    inner: std::iter::Enumerate<std::slice::IterMut<'a, S>>,
+    skip_empty: bool,
+    phantom: PhantomData<(I, II)>,
+}
+
+impl<'a, I: Item, II: ItemInstance<I>, S: Slot<I, II>> Iterator
+    for InventorySlotIteratorMut<'a, I, II, S>
+{
+    type Item = (usize, &'a mut S);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let skip_empty = self.skip_empty;
+        self.inner.find(|(_, s)| !skip_empty || s.is_occupied())
+    }
+}
+
+/// Iterates over the slots of an inventory along with their index.
+///
+/// If `skip_empty` is true, slots without an item instance are left out.
+/// ```
+/// # use game_inventory::samples::{DefaultSlot, TORCH_INST, SWORD_INST};
+/// # use game_inventory::helpers::iter_slots;
+/// let inventory = vec![DefaultSlot::new(None), DefaultSlot::new(TORCH_INST.clone()), DefaultSlot::new(SWORD_INST.clone())];
+/// assert_eq!(iter_slots(&inventory, false).count(), 3);
+/// let indices: Vec<_> = iter_slots(&inventory, true).map(|(i, _)| i).collect();
+/// assert_eq!(indices, vec![1, 2]);
+/// ```
+pub fn iter_slots<Id: Eq, I: Item<Id = Id>, II: ItemInstance<I>, S: Slot<I, II>>(
+    inventory: &[S],
+    skip_empty: bool,
+) -> InventorySlotIterator<'_, I, II, S> {
+    InventorySlotIterator {
+        inner: inventory.iter().enumerate(),
+        skip_empty,
+        phantom: PhantomData,
+    }
+}
+
+/// Like `iter_slots`, but the slots can be changed.
+///
+/// ```
+/// # use game_inventory::samples::{DefaultSlot, TORCH_INST};
+/// # use game_inventory::traits::Slot;
+/// # use game_inventory::helpers::iter_slots_mut;
+/// let mut inventory = vec![DefaultSlot::new(None), DefaultSlot::new(TORCH_INST.clone())];
+/// for (_, slot) in iter_slots_mut(&mut inventory, false) {
+///     slot.set_item_instance(&TORCH_INST);
+/// }
+/// assert_eq!(inventory[0].quantity(), 23);
+/// ```
+pub fn iter_slots_mut<Id: Eq, I: Item<Id = Id>, II: ItemInstance<I>, S: Slot<I, II>>(
+    inventory: &mut [S],
+    skip_empty: bool,
+) -> InventorySlotIteratorMut<'_, I, II, S> {
+    InventorySlotIteratorMut {
+        inner: inventory.iter_mut().enumerate(),
+        skip_empty,
+        phantom: PhantomData,
+    }
+}
+
+/// Gets the occupied slots in an inventory, along with their index.
+///
+/// ```
+/// # use game_inventory::samples::{DefaultSlot, TORCH_INST, SWORD_INST};
+/// # use game_inventory::traits::Slot;
+/// # use game_inventory::helpers::occupied;
+/// let inventory = vec![DefaultSlot::new(None), DefaultSlot::new(TORCH_INST.clone()), DefaultSlot::new(SWORD_INST.clone())];
+/// let indices: Vec<_> = occupied(&inventory).into_iter().map(|(i, _)| i).collect();
+/// assert_eq!(indices, vec![1, 2]);
+/// ```
+pub fn occupied<Id: Eq, I: Item<Id = Id>, II: ItemInstance<I>, S: Slot<I, II>>(
+    inventory: &[S],
+) -> Vec<(usize, &S)> {
+    iter_slots(inventory, true).collect()
+}
+
+/// Gets the occupied slots in an inventory mutably, along with their index.
+///
+/// ```
+/// # use game_inventory::samples::{DefaultSlot, TORCH_INST, SWORD_INST};
+/// # use game_inventory::traits::{Slot, Item};
+/// # use game_inventory::helpers::occupied_mut;
+/// let mut inventory = vec![DefaultSlot::new(TORCH_INST.clone()), DefaultSlot::new(None), DefaultSlot::new(SWORD_INST.clone())];
+/// for (_, slot) in occupied_mut(&mut inventory) {
+///     if slot.item_id() == Some("sword") {
+///         slot.set_item_instance(&None);
+///     }
+/// }
+/// assert_eq!(inventory[0].quantity(), 23);
+/// assert!(inventory[2].item_instance().is_none());
+/// ```
+pub fn occupied_mut<Id: Eq, I: Item<Id = Id>, II: ItemInstance<I>, S: Slot<I, II>>(
+    inventory: &mut [S],
+) -> Vec<(usize, &mut S)> {
+    iter_slots_mut(inventory, true).collect()
+}
+
+/// Gets the slots that hold an item with `tag`, along with their index.
+///
+/// ```
+/// # use game_inventory::samples::{DefaultSlot, DefaultItem, DefaultItemInstance, TORCH_INST};
+/// # use game_inventory::traits::ItemInstance;
+/// # use game_inventory::helpers::inventory_items_with_tag;
+/// # use std::sync::Arc;
+/// let potion = Arc::new(DefaultItem::new("fire potion", 10, "potion").with_tags(&["fire", "consumable"]));
+/// let inventory = vec![
+///     DefaultSlot::new(TORCH_INST.clone()),
+///     DefaultSlot::new(Some(DefaultItemInstance::new(potion, 3).unwrap())),
+/// ];
+/// let fire = inventory_items_with_tag(&inventory, "fire");
+/// assert_eq!(fire.len(), 1);
+/// assert_eq!(fire[0].0, 1);
+/// ```
+pub fn inventory_items_with_tag<
+    's,
+    Id: Eq,
+    I: Item<Id = Id>,
+    II: ItemInstance<I>,
+    S: Slot<I, II>,
+>(
+    inventory: &'s [S],
+    tag: &str,
+) -> Vec<(usize, &'s S)> {
+    inventory_items_with_all_tags(inventory, &[tag])
+}
+
+/// Gets the slots that hold an item with every one of `tags`, along with their index.
+///
+/// ```
+/// # use game_inventory::samples::{DefaultSlot, DefaultItem, DefaultItemInstance};
+/// # use game_inventory::traits::ItemInstance;
+/// # use game_inventory::helpers::inventory_items_with_all_tags;
+/// # use std::sync::Arc;
+/// let fire_potion = Arc::new(DefaultItem::new("fire potion", 10, "potion").with_tags(&["fire", "consumable"]));
+/// let fire_sword = Arc::new(DefaultItem::new("fire sword", 0, "weapon").with_tags(&["fire"]));
+/// let inventory = vec![
+///     DefaultSlot::new(Some(DefaultItemInstance::new(fire_sword, 0).unwrap())),
+///     DefaultSlot::new(Some(DefaultItemInstance::new(fire_potion, 3).unwrap())),
+/// ];
+/// let found = inventory_items_with_all_tags(&inventory, &["fire", "consumable"]);
+/// assert_eq!(found.iter().map(|(i, _)| *i).collect::<Vec<_>>(), vec![1]);
+/// ```
+pub fn inventory_items_with_all_tags<
+    's,
+    Id: Eq,
+    I: Item<Id = Id>,
+    II: ItemInstance<I>,
+    S: Slot<I, II>,
+>(
+    inventory: &'s [S],
+    tags: &[&str],
+) -> Vec<(usize, &'s S)> {
+    iter_slots(inventory, true)
+        .filter(|(_, s)| match s.item_instance() {
             Some(ii) => {
-                if ii.item().id() == id {
-                    if ii.item().stackable() {
-                        return ii.quant() + quant;
-                    }
-                    return quant + 1;
-                }
-                quant
+                let item = ii.item();
+                tags.iter().all(|tag| item.tags().contains(tag))
             }
-            None => quant,
+            None => false,
         })
+        .collect()
+}
+
+/// Picks a random occupied slot, with the chance of each slot being picked proportional to its quantity.
+///
+/// Unstackable items count as a quantity of `1`. Returns `None` if no slot has anything to pick.
+/// The inventory is only walked once, using weighted reservoir sampling.
+/// ```
+/// # use game_inventory::samples::{DefaultSlot, TORCH_INST, JUNK_INST};
+/// # use game_inventory::helpers::quantity_weighted_random_slot;
+/// # use rand::{rngs::StdRng, SeedableRng};
+/// let mut rng = StdRng::seed_from_u64(7);
+/// let inventory = vec![DefaultSlot::new(None), DefaultSlot::new(TORCH_INST.clone()), DefaultSlot::new(JUNK_INST.clone())];
+/// let picked = quantity_weighted_random_slot(&inventory, &mut rng).unwrap();
+/// assert!(picked == 1 || picked == 2);
+/// assert!(quantity_weighted_random_slot(&inventory[..1], &mut rng).is_none());
+/// ```
+#[cfg(feature = "rand")]
+pub fn quantity_weighted_random_slot<
+    Id: Eq,
+    I: Item<Id = Id>,
+    II: ItemInstance<I>,
+    S: Slot<I, II>,
+>(
+    inventory: &[S],
+    rng: &mut impl rand::Rng,
+) -> Option<usize> {
+    let mut total = 0u32;
+    let mut picked = None;
+    for (index, slot) in inventory.iter().enumerate() {
+        let weight = match slot.item_instance() {
+            Some(ii) if ii.item().stackable() => ii.quant() as u32,
+            Some(_) => 1,
+            None => continue,
+        };
+        if weight == 0 {
+            continue;
+        }
+        total += weight;
+        if rng.random_range(0..total) < weight {
+            picked = Some(index);
+        }
+    }
+    picked
 }
 
 /// Gets the quantity of empty slots in an inventory.
@@ -124,16 +508,144 @@ pub fn quant_in_inventory<Id: Eq, I: Item<Id = Id>, II: ItemInstance<I>, S: Slot
 /// assert_eq!(empty_quant_in_inventory(&inventory), 2)
 /// ```
 pub fn empty_quant_in_inventory<Id: Eq, I: Item<Id = Id>, II: ItemInstance<I>, S: Slot<I, II>>(
-    inventory: &Vec<S>,
+    inventory: &[S],
 ) -> usize {
-    inventory
-        .iter()
-        .filter(|slot| slot.item_instance().is_none())
-        .count()
+    inventory.iter().filter(|slot| slot.is_empty()).count()
+}
+
+/// Counts the items in an inventory by their category.
+///
+/// Stackable items count their quantity, unstackable items count as one item.
+/// ```
+/// # use game_inventory::samples::{DefaultSlot, TORCH_INST, TORCH_FULL_STACK_INST, SWORD_INST};
+/// # use game_inventory::traits::Slot;
+/// # use game_inventory::helpers::item_count_by_category;
+/// let inventory = vec![
+///     DefaultSlot::new(TORCH_INST.clone()),
+///     DefaultSlot::new(TORCH_FULL_STACK_INST.clone()),
+///     DefaultSlot::new(SWORD_INST.clone()),
+///     DefaultSlot::new(None),
+/// ];
+/// let counts = item_count_by_category(&inventory);
+/// assert_eq!(counts["tool"], 123);
+/// assert_eq!(counts["weapon"], 1);
+/// assert_eq!(counts.len(), 2);
+/// ```
+pub fn item_count_by_category<
+    Id: Eq,
+    I: Item<Id = Id> + ItemCategory,
+    II: ItemInstance<I>,
+    S: Slot<I, II>,
+>(
+    inventory: &[S],
+) -> HashMap<String, u32> {
+    let mut counts = HashMap::new();
+    for ii in inventory.iter().filter_map(|s| s.item_instance()) {
+        let item = ii.item();
+        let quant = if item.stackable() {
+            ii.quant() as u32
+        } else {
+            1
+        };
+        *counts.entry(item.category().to_owned()).or_insert(0) += quant;
+    }
+    counts
+}
+
+/// Counts the occupied slots in an inventory by the category of their item.
+///
+/// ```
+/// # use game_inventory::samples::{DefaultSlot, TORCH_INST, TORCH_FULL_STACK_INST, SWORD_INST};
+/// # use game_inventory::traits::Slot;
+/// # use game_inventory::helpers::slot_count_by_category;
+/// let inventory = vec![
+///     DefaultSlot::new(TORCH_INST.clone()),
+///     DefaultSlot::new(TORCH_FULL_STACK_INST.clone()),
+///     DefaultSlot::new(SWORD_INST.clone()),
+///     DefaultSlot::new(None),
+/// ];
+/// let counts = slot_count_by_category(&inventory);
+/// assert_eq!(counts["tool"], 2);
+/// assert_eq!(counts["weapon"], 1);
+/// assert_eq!(counts.len(), 2);
+/// ```
+pub fn slot_count_by_category<
+    Id: Eq,
+    I: Item<Id = Id> + ItemCategory,
+    II: ItemInstance<I>,
+    S: Slot<I, II>,
+>(
+    inventory: &[S],
+) -> HashMap<String, usize> {
+    let mut counts = HashMap::new();
+    for ii in inventory.iter().filter_map(|s| s.item_instance()) {
+        *counts.entry(ii.item().category().to_owned()).or_insert(0) += 1;
+    }
+    counts
+}
+
+/// Gets the total quantity of each item in an inventory, keyed by item id.
+///
+/// Stacks of the same item are summed together, and like `quant_in_inventory`
+/// unstackable items count as one item.
+/// ```
+/// # use game_inventory::samples::{DefaultSlot, TORCH, SWORD, TORCH_INST, TORCH_FULL_STACK_INST, SWORD_INST};
+/// # use game_inventory::traits::{Slot, Item};
+/// # use game_inventory::helpers::item_type_counts;
+/// let inventory = vec![
+///     DefaultSlot::new(TORCH_INST.clone()),
+///     DefaultSlot::new(SWORD_INST.clone()),
+///     DefaultSlot::new(TORCH_FULL_STACK_INST.clone()),
+///     DefaultSlot::new(SWORD_INST.clone()),
+///     DefaultSlot::new(None),
+/// ];
+/// let counts = item_type_counts(&inventory);
+/// assert_eq!(counts[&TORCH.id()], 123);
+/// assert_eq!(counts[&SWORD.id()], 2);
+/// assert_eq!(counts.len(), 2);
+/// ```
+pub fn item_type_counts<Id: Eq + Hash, I: Item<Id = Id>, II: ItemInstance<I>, S: Slot<I, II>>(
+    inventory: &[S],
+) -> HashMap<Id, u32> {
+    let mut counts = HashMap::new();
+    for ii in inventory.iter().filter_map(|s| s.item_instance()) {
+        let item = ii.item();
+        let quant = if item.stackable() {
+            ii.quant() as u32
+        } else {
+            1
+        };
+        *counts.entry(item.id()).or_insert(0) += quant;
+    }
+    counts
+}
+
+/// Gets the number of different items in an inventory.
+///
+/// ```
+/// # use game_inventory::samples::{DefaultSlot, TORCH_INST, TORCH_FULL_STACK_INST, SWORD_INST};
+/// # use game_inventory::traits::Slot;
+/// # use game_inventory::helpers::unique_item_count;
+/// let inventory = vec![
+///     DefaultSlot::new(TORCH_INST.clone()),
+///     DefaultSlot::new(SWORD_INST.clone()),
+///     DefaultSlot::new(TORCH_FULL_STACK_INST.clone()),
+///     DefaultSlot::new(None),
+/// ];
+/// assert_eq!(unique_item_count(&inventory), 2);
+/// ```
+pub fn unique_item_count<Id: Eq + Hash, I: Item<Id = Id>, II: ItemInstance<I>, S: Slot<I, II>>(
+    inventory: &[S],
+) -> usize {
+    item_type_counts(inventory).len()
 }
 
 /// Attempts to add an item to the given inventory.
 ///
+/// This never adds new slots, so it is meant for fixed size inventories that
+/// are initialized with the right number of empty slots. For inventories that
+/// grow up to a limit, see `add_to_inventory_bounded`.
+///
 /// ```
 /// # use game_inventory::samples::{DefaultSlot, TORCH_FULL_STACK_INST, SWORD_INST, TORCH_INST, TORCH, SWORD};
 /// # use game_inventory::traits::{Slot, ItemInstance, Item};
@@ -185,49 +697,163 @@ pub fn empty_quant_in_inventory<Id: Eq, I: Item<Id = Id>, II: ItemInstance<I>, S
 /// assert!(inventory[2].item_instance().unwrap().item().id() == SWORD.id());
 /// ```
 pub fn add_to_inventory<Id: Eq, I: Item<Id = Id>, II: ItemInstance<I> + Clone, S: Slot<I, II>>(
-    inventory: &mut Vec<S>,
+    inventory: &mut [S],
     other: II,
 ) -> Option<II> {
-    if inventory.capacity() == 0 {
-        return None;
-    }
+    inventory
+        .iter_mut()
+        .fold(Some(other), add_to_inventory_try_add_to_slot)
+}
 
-    return inventory
+/// Attempts to add an item to the given inventory, adding new slots as needed up to `max_slots`.
+///
+/// Existing slots are filled first. If there are items left over, a new slot is pushed
+/// to hold them, as long as the inventory has less than `max_slots` slots.
+///
+/// ```
+/// # use game_inventory::samples::{DefaultSlot, TORCH_INST, SWORD_INST, SWORD};
+/// # use game_inventory::traits::{Slot, ItemInstance, Item};
+/// # use game_inventory::helpers::add_to_inventory_bounded;
+/// let mut inventory = vec![DefaultSlot::new(TORCH_INST.clone())];
+/// let res = add_to_inventory_bounded(&mut inventory, SWORD_INST.clone().unwrap(), 2);
+/// assert!(res.unwrap().is_none());
+/// assert_eq!(inventory.len(), 2);
+/// assert!(inventory[1].item_instance().unwrap().item().id() == SWORD.id());
+/// ```
+/// Returns `InventoryError::InventoryFull` if none of the items could be added.
+/// ```
+/// # use game_inventory::samples::{DefaultSlot, TORCH_INST, SWORD_INST};
+/// # use game_inventory::helpers::add_to_inventory_bounded;
+/// # use game_inventory::errors::InventoryError;
+/// # use game_inventory::traits::Slot;
+/// let mut inventory = vec![DefaultSlot::new(TORCH_INST.clone()), DefaultSlot::new(SWORD_INST.clone())];
+/// let res = add_to_inventory_bounded(&mut inventory, SWORD_INST.clone().unwrap(), 2);
+/// assert_eq!(res.unwrap_err(), InventoryError::InventoryFull);
+/// assert_eq!(inventory.len(), 2);
+/// ```
+/// If only some of the items could be added, the rest are returned.
+/// ```
+/// # use game_inventory::samples::{DefaultSlot, DefaultItemInstance, TORCH, TORCH_INST};
+/// # use game_inventory::traits::{Slot, ItemInstance};
+/// # use game_inventory::helpers::add_to_inventory_bounded;
+/// # use std::sync::Arc;
+/// let mut inventory = vec![DefaultSlot::new(TORCH_INST.clone())];
+/// let res = add_to_inventory_bounded(&mut inventory, DefaultItemInstance::new(Arc::new(TORCH.clone()), 80).unwrap(), 1);
+/// assert_eq!(res.unwrap().unwrap().quant(), 3);
+/// assert_eq!(inventory[0].quantity(), 100);
+/// ```
+pub fn add_to_inventory_bounded<
+    Id: Eq,
+    I: Item<Id = Id>,
+    II: ItemInstance<I> + Clone,
+    S: Slot<I, II>,
+>(
+    inventory: &mut Vec<S>,
+    other: II,
+    max_slots: usize,
+) -> Result<Option<II>, InventoryError> {
+    let quant = other.quant();
+    let mut res = inventory
         .iter_mut()
         .fold(Some(other), add_to_inventory_try_add_to_slot);
+    if res.is_some() && inventory.len() < max_slots {
+        let mut slot = S::new(None);
+        res = add_to_inventory_try_add_to_slot(res, &mut slot);
+        inventory.push(slot);
+    }
+    match res {
+        Some(r) if r.quant() == quant => Err(InventoryError::InventoryFull),
+        r => Ok(r),
+    }
 }
 
-fn add_to_inventory_try_add_to_slot<
+/// Attempts to add an item to several inventories, in order of priority.
+///
+/// Each inventory is filled with `add_to_inventory` before moving on to the next,
+/// so items go into a dedicated pouch or belt before spilling into the main bag.
+/// Returns whatever did not fit in any of them.
+///
+/// ```
+/// # use game_inventory::samples::{DefaultSlot, DefaultItemInstance, TORCH, TORCH_INST, JUNK_INST};
+/// # use game_inventory::traits::{Slot, ItemInstance};
+/// # use game_inventory::helpers::priority_add_to_inventory;
+/// # use std::sync::Arc;
+/// let mut pouch = vec![DefaultSlot::new(TORCH_INST.clone())];
+/// let mut bag = vec![DefaultSlot::new(JUNK_INST.clone()), DefaultSlot::new(None)];
+/// let res = priority_add_to_inventory(
+///     &mut [&mut pouch, &mut bag],
+///     DefaultItemInstance::new(Arc::new(TORCH.clone()), 90).unwrap(),
+/// );
+/// assert!(res.is_none());
+/// assert_eq!(pouch[0].quantity(), 100);
+/// assert_eq!(bag[0].quantity(), 91);
+/// assert_eq!(bag[1].quantity(), 13);
+/// ```
+/// If none of the inventories have room, the leftover items are returned.
+/// ```
+/// # use game_inventory::samples::{DefaultSlot, SWORD_INST, TORCH_INST};
+/// # use game_inventory::helpers::priority_add_to_inventory;
+/// # use game_inventory::traits::Slot;
+/// let mut pouch = vec![DefaultSlot::new(TORCH_INST.clone())];
+/// let mut bag = vec![DefaultSlot::new(SWORD_INST.clone())];
+/// let res = priority_add_to_inventory(&mut [&mut pouch, &mut bag], SWORD_INST.clone().unwrap());
+/// assert!(res.is_some());
+/// ```
+pub fn priority_add_to_inventory<
     Id: Eq,
     I: Item<Id = Id>,
     II: ItemInstance<I> + Clone,
     S: Slot<I, II>,
 >(
-    other: Option<II>,
-    slot: &mut S,
+    inventories: &mut [&mut [S]],
+    other: II,
+) -> Option<II> {
+    inventories
+        .iter_mut()
+        .try_fold(other, |other, inventory| add_to_inventory(inventory, other))
+}
+
+fn add_to_inventory_try_add_to_slot<
+    Id: Eq,
+    I: Item<Id = Id>,
+    II: ItemInstance<I> + Clone,
+    S: Slot<I, II>,
+>(
+    other: Option<II>,
+    slot: &mut S,
 ) -> Option<II> {
     let c = match &other {
         None => return None,
         Some(c) => c,
     };
+    if !slot.accepts(&c.item()) {
+        return other;
+    }
 
     let s = match slot.item_instance() {
         None => {
-            slot.set_item_instance(&other);
-            return None;
+            let capacity = slot.capacity_for(&c.item());
+            if !c.item().stackable() || c.quant() <= capacity {
+                slot.set_item_instance(&other);
+                return None;
+            }
+            if capacity == 0 {
+                return other;
+            }
+            slot.set_item_instance(&Some(II::new(c.item(), capacity)));
+            return Some(II::new(c.item(), c.quant() - capacity));
         }
         Some(s) => s,
     };
 
-    if s.item().id() != c.item().id() {
+    if !s.item().stackable_with(c) {
         return other;
     }
-    if s.quant() == s.item().max_quant() {
-        return other;
+    match slot.try_add_quantity(c.quant()) {
+        0 => None,
+        left if left == c.quant() => other,
+        left => Some(II::new(c.item(), left)),
     }
-    let res = unwrap_items_res(combine_stack((slot.item_instance(), Some(c.clone()))));
-    slot.set_item_instance(&res.1);
-    return res.0;
 }
 
 /// Attempts to remove an item from the given inventory.
@@ -246,7 +872,7 @@ fn add_to_inventory_try_add_to_slot<
 ///     DefaultSlot::new(TORCH_INST.clone()),
 ///     DefaultSlot::new(None),
 /// ];
-/// assert!(remove_from_inventory(&mut inventory, DefaultItemInstance::new(Arc::new(TORCH.clone()), 123)).is_none());
+/// assert!(remove_from_inventory(&mut inventory, DefaultItemInstance::new_unchecked(Arc::new(TORCH.clone()), 123)).is_none());
 /// assert_eq!(quant_in_inventory(&inventory, TORCH.id()), 0)
 /// ```
 /// Does not use unstackable items `.quant()` method, treats every unstackable item as one removal.
@@ -259,9 +885,9 @@ fn add_to_inventory_try_add_to_slot<
 ///     DefaultSlot::new(TORCH_FULL_STACK_INST.clone()),
 ///     DefaultSlot::new(SWORD_INST.clone()),
 ///     DefaultSlot::new(TORCH_INST.clone()),
-///     DefaultSlot::new(Some(DefaultItemInstance::new(Arc::new(SWORD.clone()), 123))),
+///     DefaultSlot::new(Some(DefaultItemInstance::new_unchecked(Arc::new(SWORD.clone()), 123))),
 /// ];
-/// assert!(remove_from_inventory(&mut inventory, DefaultItemInstance::new(Arc::new(SWORD.clone()), 2)).is_none());
+/// assert!(remove_from_inventory(&mut inventory, DefaultItemInstance::new_unchecked(Arc::new(SWORD.clone()), 2)).is_none());
 /// assert_eq!(quant_in_inventory(&inventory, SWORD.id()), 0);
 /// ```
 /// If the inventory does not have that may items to remove,
@@ -277,12 +903,13 @@ fn add_to_inventory_try_add_to_slot<
 ///     DefaultSlot::new(None),
 ///     DefaultSlot::new(None),
 /// ];
-/// let res = remove_from_inventory(&mut inventory, DefaultItemInstance::new(Arc::new(TORCH.clone()), 123)).unwrap();
+/// let res = remove_from_inventory(&mut inventory, DefaultItemInstance::new_unchecked(Arc::new(TORCH.clone()), 123)).unwrap();
 /// assert_eq!(res.item().id(), TORCH.id());
 /// assert_eq!(res.quant(), 23);
 /// assert_eq!(quant_in_inventory(&inventory, TORCH.id()), 0);
 /// ```
 /// Guarantees that items not requested to be removed will remain untouched.
+/// Stacks are only taken from if their item is `stackable_with` the item being removed.
 /// ```
 /// # use game_inventory::samples::{DefaultSlot, DefaultItemInstance, TORCH_FULL_STACK_INST, SWORD_INST, TORCH, TORCH_INST};
 /// # use game_inventory::traits::{Slot, ItemInstance, Item};
@@ -294,11 +921,11 @@ fn add_to_inventory_try_add_to_slot<
 ///     DefaultSlot::new(TORCH_INST.clone()),
 ///     DefaultSlot::new(None),
 /// ];
-/// assert!(remove_from_inventory(&mut inventory, DefaultItemInstance::new(Arc::new(TORCH.clone()), 100)).is_none());
+/// assert!(remove_from_inventory(&mut inventory, DefaultItemInstance::new(Arc::new(TORCH.clone()), 100).unwrap()).is_none());
 /// assert_eq!(quant_in_inventory(&inventory, TORCH.id()), 23);
 /// ```
 pub fn remove_from_inventory<Id: Eq, I: Item<Id = Id>, II: ItemInstance<I>, S: Slot<I, II>>(
-    inventory: &mut Vec<S>,
+    inventory: &mut [S],
     other: II,
 ) -> Option<II> {
     let remaining = inventory.iter_mut().fold(other.quant(), |current, slot| {
@@ -307,7 +934,7 @@ pub fn remove_from_inventory<Id: Eq, I: Item<Id = Id>, II: ItemInstance<I>, S: S
     if remaining == 0 {
         return None;
     }
-    return Some(II::new(other.item(), remaining));
+    Some(II::new(other.item(), remaining))
 }
 
 fn remove_from_inventory_try_remove<
@@ -327,13 +954,16 @@ fn remove_from_inventory_try_remove<
     if current == 0 {
         return 0;
     }
-    if s.item().id() != other.item().id() {
-        return current;
-    }
     if !s.item().stackable() {
+        if s.item().id() != other.item().id() {
+            return current;
+        }
         slot.set_item_instance(&None);
         return current - 1;
     }
+    if !s.item().stackable_with(other) {
+        return current;
+    }
     if s.quant() <= current {
         slot.set_item_instance(&None);
         return current - s.quant();
@@ -359,7 +989,7 @@ fn remove_from_inventory_try_remove<
 ///     DefaultSlot::new(TORCH_INST.clone()),
 ///     DefaultSlot::new(None),
 /// ];
-/// let can_add = can_add_item_to_inventory(&inventory, DefaultItemInstance::new(Arc::new(TORCH.clone()), 10));
+/// let can_add = can_add_item_to_inventory(&inventory, DefaultItemInstance::new(Arc::new(TORCH.clone()), 10).unwrap());
 /// assert!(can_add);
 /// ```
 ///
@@ -373,7 +1003,7 @@ fn remove_from_inventory_try_remove<
 ///     DefaultSlot::new(SWORD_INST.clone()),
 ///     DefaultSlot::new(None),
 /// ];
-/// let can_add = can_add_item_to_inventory(&inventory, DefaultItemInstance::new(Arc::new(SWORD.clone()), 1));
+/// let can_add = can_add_item_to_inventory(&inventory, DefaultItemInstance::new_unchecked(Arc::new(SWORD.clone()), 1));
 /// assert!(can_add);
 /// ```
 ///
@@ -387,7 +1017,7 @@ fn remove_from_inventory_try_remove<
 ///     DefaultSlot::new(TORCH_FULL_STACK_INST.clone()),
 ///     DefaultSlot::new(SWORD_INST.clone()),
 /// ];
-/// let can_add = can_add_item_to_inventory(&inventory, DefaultItemInstance::new(Arc::new(TORCH.clone()), 1));
+/// let can_add = can_add_item_to_inventory(&inventory, DefaultItemInstance::new(Arc::new(TORCH.clone()), 1).unwrap());
 /// assert!(!can_add);
 /// ```
 ///
@@ -398,10 +1028,10 @@ fn remove_from_inventory_try_remove<
 /// # use game_inventory::traits::{Slot, ItemInstance, Item};
 /// # use game_inventory::helpers::can_add_item_to_inventory;
 /// let inventory = vec![
-///     DefaultSlot::new(Some(DefaultItemInstance::new(Arc::new(TORCH.clone()), 50))),
-///     DefaultSlot::new(Some(DefaultItemInstance::new(Arc::new(JUNK.clone()), 30))),
+///     DefaultSlot::new(Some(DefaultItemInstance::new(Arc::new(TORCH.clone()), 50).unwrap())),
+///     DefaultSlot::new(Some(DefaultItemInstance::new(Arc::new(JUNK.clone()), 30).unwrap())),
 /// ];
-/// let can_add = can_add_item_to_inventory(&inventory, DefaultItemInstance::new(Arc::new(TORCH.clone()), 50));
+/// let can_add = can_add_item_to_inventory(&inventory, DefaultItemInstance::new(Arc::new(TORCH.clone()), 50).unwrap());
 /// assert!(can_add);
 /// ```
 ///
@@ -412,19 +1042,19 @@ fn remove_from_inventory_try_remove<
 /// # use game_inventory::traits::{Slot, ItemInstance, Item};
 /// # use game_inventory::helpers::can_add_item_to_inventory;
 /// let inventory = vec![
-///     DefaultSlot::new(Some(DefaultItemInstance::new(Arc::new(TORCH.clone()), 70))),
+///     DefaultSlot::new(Some(DefaultItemInstance::new(Arc::new(TORCH.clone()), 70).unwrap())),
 /// ];
-/// let can_add = can_add_item_to_inventory(&inventory, DefaultItemInstance::new(Arc::new(TORCH.clone()), 31));
+/// let can_add = can_add_item_to_inventory(&inventory, DefaultItemInstance::new(Arc::new(TORCH.clone()), 31).unwrap());
 /// assert!(!can_add);
 /// ```
 pub fn can_add_item_to_inventory<Id: Eq, I: Item<Id = Id>, II: ItemInstance<I>, S: Slot<I, II>>(
-    inventory: &Vec<S>,
+    inventory: &[S],
     other: II,
 ) -> bool {
     if !other.item().stackable() {
-        return can_add_unstackable_item_to_inventory(inventory);
+        return can_add_unstackable_item_to_inventory(inventory, &other);
     }
-    return can_add_stackable_item_to_inventory(inventory, other);
+    can_add_stackable_item_to_inventory(inventory, other)
 }
 
 fn can_add_unstackable_item_to_inventory<
@@ -433,53 +1063,1542 @@ fn can_add_unstackable_item_to_inventory<
     II: ItemInstance<I>,
     S: Slot<I, II>,
 >(
-    inventory: &Vec<S>,
+    inventory: &[S],
+    other: &II,
 ) -> bool {
-    inventory.iter().any(|slot| slot.item_instance().is_none())
+    inventory
+        .iter()
+        .any(|slot| slot.is_empty() && slot.accepts(&other.item()))
 }
 
+/// Goes through the slots the same way `add_to_inventory` does, without changing them.
 fn can_add_stackable_item_to_inventory<
     Id: Eq,
     I: Item<Id = Id>,
     II: ItemInstance<I>,
     S: Slot<I, II>,
 >(
-    inventory: &Vec<S>,
-    mut other: II,
+    inventory: &[S],
+    other: II,
 ) -> bool {
-    let max_stack = other.item().max_quant();
-    if other.quant() == max_stack {
-        return can_add_unstackable_item_to_inventory(inventory);
-    }
-
+    let mut remaining = other.quant();
     for slot in inventory {
-        let item = match slot.item_instance() {
-            None => {
-                return true;
+        if !slot.accepts(&other.item()) {
+            continue;
+        }
+        let space = match slot.item_instance() {
+            None => slot.capacity_for(&other.item()),
+            Some(item) if item.item().stackable_with(&other) => {
+                slot.capacity().saturating_sub(item.quant())
             }
-            Some(x) => x,
+            Some(_) => continue,
         };
+        if remaining <= space {
+            return true;
+        }
+        remaining -= space;
+    }
+    false
+}
 
-        if item.item().id() == other.item().id() {
-            if item.quant() == max_stack {
-                continue;
-            }
-            if item.quant() + other.quant() <= max_stack {
-                return true;
-            }
+/// The order to sort an inventory in, see `sort_inventory_by`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ItemSort {
+    /// By item id, smallest first.
+    Id,
+    /// By quantity, smallest first.
+    Quantity,
+    /// By quantity, largest first.
+    QuantityDescending,
+}
+
+/// Compares two item instances by the given sort order.
+pub(crate) fn compare_item_instances<Id: Ord, I: Item<Id = Id>, II: ItemInstance<I>>(
+    sort: ItemSort,
+    a: &II,
+    b: &II,
+) -> Ordering {
+    match sort {
+        ItemSort::Id => a.item().id().cmp(&b.item().id()),
+        ItemSort::Quantity => a.quant().cmp(&b.quant()),
+        ItemSort::QuantityDescending => b.quant().cmp(&a.quant()),
+    }
+}
+
+/// Sorts the items in an inventory, moving all empty slots to the end.
+///
+/// The sort is stable, so items that compare equal keep their order.
+/// Items are moved between slots, the slots themselves stay where they are.
+///
+/// ```
+/// # use game_inventory::samples::{DefaultSlot, TORCH, JUNK, SWORD, TORCH_INST, JUNK_INST, SWORD_INST, TORCH_FULL_STACK_INST};
+/// # use game_inventory::traits::{Slot, Item};
+/// # use game_inventory::helpers::{sort_inventory_by, ItemSort};
+/// let mut inventory = vec![
+///     DefaultSlot::new(TORCH_INST.clone()),
+///     DefaultSlot::new(None),
+///     DefaultSlot::new(SWORD_INST.clone()),
+///     DefaultSlot::new(JUNK_INST.clone()),
+///     DefaultSlot::new(TORCH_FULL_STACK_INST.clone()),
+/// ];
+/// sort_inventory_by(&mut inventory, ItemSort::Id);
+/// let ids: Vec<_> = inventory.iter().map(|s| s.item_id()).collect();
+/// assert_eq!(ids, vec![Some(JUNK.id()), Some(SWORD.id()), Some(TORCH.id()), Some(TORCH.id()), None]);
+/// assert_eq!(inventory[2].quantity(), 23);
+///
+/// sort_inventory_by(&mut inventory, ItemSort::QuantityDescending);
+/// let quants: Vec<_> = inventory.iter().map(|s| s.quantity()).collect();
+/// assert_eq!(quants, vec![100, 91, 23, 0, 0]);
+/// assert!(inventory[4].item_instance().is_none());
+/// ```
+pub fn sort_inventory_by<Id: Ord, I: Item<Id = Id>, II: ItemInstance<I>, S: Slot<I, II>>(
+    inventory: &mut [S],
+    sort: ItemSort,
+) {
+    let mut item_instances: Vec<II> = inventory.iter().filter_map(|s| s.item_instance()).collect();
+    item_instances.sort_by(|a, b| compare_item_instances(sort, a, b));
+    let mut item_instances = item_instances.into_iter();
+    for slot in inventory.iter_mut() {
+        slot.set_item_instance(&item_instances.next());
+    }
+}
+
+/// Puts the items of an inventory into the order given, like when the player drags items around.
+///
+/// `new_order[i] = Some(j)` puts the item that is at index `j` into slot `i`, `None` leaves slot `i` empty.
+/// Nothing is changed if `new_order` is not as long as the inventory, uses an index twice,
+/// or leaves out a slot that holds an item.
+/// The items are moved along each cycle of the order, so each slot is only set once.
+/// This runs in `O(n)` time but is not allocation free: checking the order and tracking which
+/// slots have been visited uses two vectors as long as the inventory.
+/// ```
+/// # use game_inventory::samples::{DefaultSlot, TORCH_INST, JUNK_INST, SWORD_INST};
+/// # use game_inventory::traits::{Item, ItemInstance, Slot};
+/// # use game_inventory::helpers::reorder_inventory;
+/// # use game_inventory::errors::ReorderError;
+/// let mut inventory = vec![
+///     DefaultSlot::new(TORCH_INST.clone()),
+///     DefaultSlot::new(JUNK_INST.clone()),
+///     DefaultSlot::new(None),
+///     DefaultSlot::new(SWORD_INST.clone()),
+/// ];
+/// reorder_inventory(&mut inventory, &[Some(3), None, Some(0), Some(1)]).unwrap();
+/// let ids: Vec<_> = inventory.iter().map(|s| s.item_instance().map(|i| i.item().id())).collect();
+/// assert_eq!(ids, vec![Some("sword"), None, Some("torch"), Some("junk")]);
+/// assert_eq!(
+///     reorder_inventory(&mut inventory, &[Some(0), Some(0), Some(2), Some(3)]),
+///     Err(ReorderError::DuplicateIndex(0))
+/// );
+/// assert_eq!(
+///     reorder_inventory(&mut inventory, &[None, None, Some(2), Some(3)]),
+///     Err(ReorderError::ItemLost(0))
+/// );
+/// ```
+pub fn reorder_inventory<Id: Eq, I: Item<Id = Id>, II: ItemInstance<I>, S: Slot<I, II>>(
+    inventory: &mut [S],
+    new_order: &[Option<usize>],
+) -> Result<(), ReorderError> {
+    if new_order.len() != inventory.len() {
+        return Err(ReorderError::LengthMismatch {
+            expected: inventory.len(),
+            found: new_order.len(),
+        });
+    }
+    let mut used = vec![false; inventory.len()];
+    for &from in new_order.iter().flatten() {
+        match used.get_mut(from) {
+            None => return Err(ReorderError::IndexOutOfBounds(from)),
+            Some(true) => return Err(ReorderError::DuplicateIndex(from)),
+            Some(u) => *u = true,
+        }
+    }
+    if let Some(lost) = (0..inventory.len()).find(|&i| !used[i] && inventory[i].is_occupied()) {
+        return Err(ReorderError::ItemLost(lost));
+    }
+    // The slots that are left out are all empty, so they can fill the `None`s to make a permutation.
+    let mut unused = (0..inventory.len()).filter(|&i| !used[i]);
+    let mut order: Vec<usize> = new_order
+        .iter()
+        .map(|from| from.unwrap_or_else(|| unused.next().unwrap()))
+        .collect();
+    for start in 0..order.len() {
+        if order[start] == start || order[start] == usize::MAX {
+            continue;
+        }
+        let first = inventory[start].item_instance();
+        let mut to = start;
+        while order[to] != start {
+            let from = order[to];
+            inventory[to].set_item_instance(&inventory[from].item_instance());
+            order[to] = usize::MAX;
+            to = from;
+        }
+        inventory[to].set_item_instance(&first);
+        order[to] = usize::MAX;
+    }
+    Ok(())
+}
+
+/// A search over an inventory built out of several criteria.
+///
+/// Every criterion that is added must match for a slot to be part of the result.
+/// Criteria for data specific to your game can be added with `matching`.
+///
+/// ```
+/// # use game_inventory::samples::{DefaultSlot, TORCH, TORCH_INST, TORCH_FULL_STACK_INST, SWORD_INST};
+/// # use game_inventory::traits::{Slot, Item};
+/// # use game_inventory::helpers::InventoryQuery;
+/// let inventory = vec![
+///     DefaultSlot::new(TORCH_INST.clone()),
+///     DefaultSlot::new(SWORD_INST.clone()),
+///     DefaultSlot::new(None),
+///     DefaultSlot::new(TORCH_FULL_STACK_INST.clone()),
+/// ];
+/// let res = InventoryQuery::new()
+///     .with_item_id(TORCH.id())
+///     .with_min_quantity(50)
+///     .execute(&inventory);
+/// assert_eq!(res.len(), 1);
+/// assert_eq!(res[0].0, 3);
+///
+/// let weapons = InventoryQuery::new().with_category("weapon").execute(&inventory);
+/// assert_eq!(weapons.iter().map(|(i, _)| *i).collect::<Vec<_>>(), vec![1]);
+///
+/// let occupied = InventoryQuery::new().occupied_only().execute(&inventory);
+/// assert_eq!(occupied.iter().map(|(i, _)| *i).collect::<Vec<_>>(), vec![0, 1, 3]);
+///
+/// let custom = InventoryQuery::new()
+///     .matching(|s: &DefaultSlot<_, _>| s.quantity() % 2 == 1)
+///     .execute(&inventory);
+/// assert_eq!(custom.len(), 1);
+/// ```
+pub struct InventoryQuery<'a, S> {
+    predicates: Vec<SlotPredicate<'a, S>>,
+}
+
+type SlotPredicate<'a, S> = Box<dyn Fn(&S) -> bool + 'a>;
 
-            let space_available = max_stack - item.quant();
-            other = II::new(other.item(), space_available);
+impl<'a, S> InventoryQuery<'a, S> {
+    /// Creates a query that matches every slot.
+    pub fn new() -> Self {
+        InventoryQuery {
+            predicates: Vec::new(),
         }
     }
 
-    if other.quant() == 0 {
-        return true;
+    /// Only matches slots that the predicate returns true for.
+    pub fn matching<F: Fn(&S) -> bool + 'a>(mut self, predicate: F) -> Self {
+        self.predicates.push(Box::new(predicate));
+        self
     }
 
-    if other.quant() > 0 {
-        return can_add_unstackable_item_to_inventory(inventory);
+    /// Only matches slots that hold an item.
+    pub fn occupied_only<Id: Eq, I: Item<Id = Id>, II: ItemInstance<I>>(self) -> Self
+    where
+        S: Slot<I, II>,
+    {
+        self.matching(|s: &S| s.is_occupied())
     }
 
-    false
+    /// Only matches slots that hold an item with a matching id.
+    pub fn with_item_id<Id: Eq + 'a, I: Item<Id = Id>, II: ItemInstance<I>>(self, id: Id) -> Self
+    where
+        S: Slot<I, II>,
+    {
+        self.matching(move |s: &S| match s.item_instance() {
+            Some(i) => i.item().id() == id,
+            None => false,
+        })
+    }
+
+    /// Only matches slots that hold an item in `category`, see `ItemCategory`.
+    pub fn with_category<Id: Eq, I: Item<Id = Id> + ItemCategory, II: ItemInstance<I>>(
+        self,
+        category: &'a str,
+    ) -> Self
+    where
+        S: Slot<I, II>,
+    {
+        self.matching(move |s: &S| match s.item_instance() {
+            Some(i) => i.item().category() == category,
+            None => false,
+        })
+    }
+
+    /// Only matches slots that hold at least `quantity` items.
+    pub fn with_min_quantity<Id: Eq, I: Item<Id = Id>, II: ItemInstance<I>>(
+        self,
+        quantity: u16,
+    ) -> Self
+    where
+        S: Slot<I, II>,
+    {
+        self.matching(move |s: &S| match s.item_instance() {
+            Some(i) => i.quant() >= quantity,
+            None => false,
+        })
+    }
+
+    /// Runs the query, returning every matching slot along with its index.
+    pub fn execute<'s>(&self, inventory: &'s [S]) -> Vec<(usize, &'s S)> {
+        inventory
+            .iter()
+            .enumerate()
+            .filter(|(_, s)| self.predicates.iter().all(|p| p(s)))
+            .collect()
+    }
+}
+
+impl<'a, S> Default for InventoryQuery<'a, S> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// How `find_item_to_drop` picks the slot to clear.
+///
+/// There is no variant for dropping the least valuable item, since `find_item_to_drop`
+/// would then need every item to implement `ItemValue`. Use `DropStrategy::least_valuable`.
+pub enum DropStrategy<'a, S> {
+    /// The slot with the fewest items, unstackable items count as one item.
+    SmallestStack,
+    /// The first occupied slot in the inventory.
+    OldestSlot,
+    /// The slot that is given the lowest score.
+    Custom(SlotScore<'a, S>),
+}
+
+type SlotScore<'a, S> = Box<dyn Fn(&S) -> f32 + 'a>;
+
+impl<'a, S> DropStrategy<'a, S> {
+    /// Picks the slot whose items are worth the least altogether, see `ItemValue`.
+    ///
+    /// Stacks count their quantity, unstackable items count as one item.
+    /// ```
+    /// # use game_inventory::samples::{DefaultSlot, DefaultItem, DefaultItemInstance};
+    /// # use game_inventory::helpers::{find_item_to_drop, DropStrategy};
+    /// # use std::sync::Arc;
+    /// let gem = Arc::new(DefaultItem::new("gem", 10, "treasure").with_value(100));
+    /// let crown = Arc::new(DefaultItem::new("crown", 0, "treasure").with_value(250));
+    /// let inventory = vec![
+    ///     DefaultSlot::new(Some(DefaultItemInstance::new(gem.clone(), 3).unwrap())),
+    ///     DefaultSlot::new(Some(DefaultItemInstance::new(crown, 0).unwrap())),
+    ///     DefaultSlot::new(Some(DefaultItemInstance::new(gem, 2).unwrap())),
+    /// ];
+    /// assert_eq!(find_item_to_drop(&inventory, DropStrategy::least_valuable()), Some(2));
+    /// ```
+    pub fn least_valuable<Id: Eq, I: Item<Id = Id> + ItemValue, II: ItemInstance<I>>() -> Self
+    where
+        S: Slot<I, II> + 'a,
+    {
+        DropStrategy::Custom(Box::new(|s: &S| match s.item_instance() {
+            Some(i) if i.item().stackable() => i.item().value() as f32 * i.quant() as f32,
+            Some(i) => i.item().value() as f32,
+            None => 0.0,
+        }))
+    }
+}
+
+/// Suggests which slot to clear to make room in a full inventory.
+///
+/// Only occupied slots are considered, if several slots are tied the first one is picked.
+/// Returns `None` if there are no items in the inventory.
+/// ```
+/// # use game_inventory::samples::{DefaultSlot, TORCH_INST, TORCH_FULL_STACK_INST, SWORD_INST};
+/// # use game_inventory::traits::Slot;
+/// # use game_inventory::helpers::{find_item_to_drop, DropStrategy};
+/// let inventory = vec![
+///     DefaultSlot::new(None),
+///     DefaultSlot::new(TORCH_FULL_STACK_INST.clone()),
+///     DefaultSlot::new(TORCH_INST.clone()),
+///     DefaultSlot::new(SWORD_INST.clone()),
+/// ];
+/// assert_eq!(find_item_to_drop(&inventory, DropStrategy::OldestSlot), Some(1));
+/// assert_eq!(find_item_to_drop(&inventory, DropStrategy::SmallestStack), Some(3));
+/// let by_quantity = DropStrategy::Custom(Box::new(|s: &DefaultSlot<_, _>| -(s.quantity() as f32)));
+/// assert_eq!(find_item_to_drop(&inventory, by_quantity), Some(1));
+/// assert_eq!(find_item_to_drop(&inventory[..1], DropStrategy::OldestSlot), None);
+/// ```
+pub fn find_item_to_drop<Id: Eq, I: Item<Id = Id>, II: ItemInstance<I>, S: Slot<I, II>>(
+    inventory: &[S],
+    strategy: DropStrategy<S>,
+) -> Option<usize> {
+    let mut occupied = occupied(inventory).into_iter();
+    let res = match strategy {
+        DropStrategy::SmallestStack => occupied.min_by_key(|(_, s)| match s.item_instance() {
+            Some(i) if !i.item().stackable() => 1,
+            _ => s.quantity(),
+        }),
+        DropStrategy::OldestSlot => occupied.next(),
+        DropStrategy::Custom(score) => {
+            occupied.min_by(|(_, a), (_, b)| score(a).total_cmp(&score(b)))
+        }
+    };
+    res.map(|(i, _)| i)
+}
+
+/// Replaces every item instance in an inventory with what `f` returns for it.
+///
+/// Returning `None` clears the slot. Empty slots are skipped. Every occupied slot is
+/// set again with `set_item_instance` so its callbacks run.
+/// ```
+/// # use game_inventory::samples::{DefaultSlot, DefaultItemInstance, TORCH_INST, SWORD_INST, TORCH_FULL_STACK_INST};
+/// # use game_inventory::traits::{Slot, Item, ItemInstance};
+/// # use game_inventory::helpers::map_inventory;
+/// let mut inventory = vec![
+///     DefaultSlot::new(TORCH_INST.clone()),
+///     DefaultSlot::new(SWORD_INST.clone()),
+///     DefaultSlot::new(None),
+///     DefaultSlot::new(TORCH_FULL_STACK_INST.clone()),
+/// ];
+/// map_inventory(&mut inventory, Some);
+/// assert_eq!(inventory.iter().map(|s| s.quantity()).collect::<Vec<_>>(), vec![23, 0, 0, 100]);
+/// map_inventory(&mut inventory, |i| match i.item().stackable() {
+///     true => Some(DefaultItemInstance::new(i.item(), i.quant() / 2).unwrap()),
+///     false => None,
+/// });
+/// assert_eq!(inventory.iter().map(|s| s.quantity()).collect::<Vec<_>>(), vec![11, 0, 0, 50]);
+/// assert!(inventory[1].item_instance().is_none());
+/// map_inventory(&mut inventory, |_| None);
+/// assert!(inventory.iter().all(|s| s.item_instance().is_none()));
+/// ```
+pub fn map_inventory<
+    Id: Eq,
+    I: Item<Id = Id>,
+    II: ItemInstance<I>,
+    S: Slot<I, II>,
+    F: FnMut(II) -> Option<II>,
+>(
+    inventory: &mut [S],
+    mut f: F,
+) {
+    for slot in inventory.iter_mut() {
+        if let Some(ii) = slot.item_instance() {
+            slot.set_item_instance(&f(ii));
+        }
+    }
+}
+
+/// Gets what is still missing from an inventory to fulfill a list of requirements.
+///
+/// Each requirement is an item id and the quantity needed, counted the same
+/// way as `item_total_quantity`. Requirements that are met are left out, so an
+/// empty result means everything is there.
+/// ```
+/// # use game_inventory::samples::{DefaultSlot, TORCH, SWORD, JUNK, TORCH_INST, SWORD_INST};
+/// # use game_inventory::traits::{Slot, Item};
+/// # use game_inventory::helpers::inventory_difference;
+/// let inventory = vec![
+///     DefaultSlot::new(TORCH_INST.clone()),
+///     DefaultSlot::new(SWORD_INST.clone()),
+///     DefaultSlot::new(None),
+/// ];
+/// let missing = inventory_difference(&inventory, &[(TORCH.id(), 30), (SWORD.id(), 1), (JUNK.id(), 5)]);
+/// assert_eq!(missing, vec![(TORCH.id(), 7), (JUNK.id(), 5)]);
+/// assert!(inventory_difference(&inventory, &[(TORCH.id(), 23)]).is_empty());
+/// ```
+pub fn inventory_difference<
+    Id: Eq + Clone,
+    I: Item<Id = Id>,
+    II: ItemInstance<I>,
+    S: Slot<I, II>,
+>(
+    inventory: &[S],
+    requirements: &[(Id, u16)],
+) -> Vec<(Id, u16)> {
+    requirements
+        .iter()
+        .filter_map(|(id, needed)| {
+            let have = item_total_quantity(inventory, id.clone());
+            let missing = (*needed as u32).saturating_sub(have) as u16;
+            (missing > 0).then(|| (id.clone(), missing))
+        })
+        .collect()
+}
+
+/// Gets the items that are in both inventories, with the smaller of the two quantities.
+///
+/// Quantities are counted the same way as `item_total_quantity`,
+/// and the items are in the order they first appear in `a`.
+/// ```
+/// # use game_inventory::samples::{DefaultSlot, TORCH, JUNK, TORCH_INST, TORCH_FULL_STACK_INST, JUNK_INST, SWORD_INST};
+/// # use game_inventory::traits::{Slot, Item};
+/// # use game_inventory::helpers::inventory_intersection;
+/// let a = vec![DefaultSlot::new(TORCH_FULL_STACK_INST.clone()), DefaultSlot::new(JUNK_INST.clone())];
+/// let b = vec![DefaultSlot::new(SWORD_INST.clone()), DefaultSlot::new(TORCH_INST.clone())];
+/// assert_eq!(inventory_intersection(&a, &b), vec![(TORCH.id(), 23)]);
+/// ```
+pub fn inventory_intersection<
+    Id: Eq + Clone,
+    I: Item<Id = Id>,
+    II: ItemInstance<I>,
+    S: Slot<I, II>,
+>(
+    a: &[S],
+    b: &[S],
+) -> Vec<(Id, u32)> {
+    let b = quantities_by_id(b);
+    quantities_by_id(a)
+        .into_iter()
+        .filter_map(|(id, quant)| {
+            let (_, other) = b.iter().find(|(other, _)| *other == id)?;
+            Some((id, quant.min(*other)))
+        })
+        .collect()
+}
+
+/// Gets the items that are in either inventory, with the larger of the two quantities.
+///
+/// Items from `a` come first, followed by the ones only in `b`.
+/// ```
+/// # use game_inventory::samples::{DefaultSlot, TORCH, JUNK, SWORD, TORCH_INST, TORCH_FULL_STACK_INST, JUNK_INST, SWORD_INST};
+/// # use game_inventory::traits::{Slot, Item};
+/// # use game_inventory::helpers::inventory_union;
+/// let a = vec![DefaultSlot::new(TORCH_INST.clone()), DefaultSlot::new(JUNK_INST.clone())];
+/// let b = vec![DefaultSlot::new(SWORD_INST.clone()), DefaultSlot::new(TORCH_FULL_STACK_INST.clone())];
+/// assert_eq!(
+///     inventory_union(&a, &b),
+///     vec![(TORCH.id(), 100), (JUNK.id(), 91), (SWORD.id(), 1)]
+/// );
+/// ```
+pub fn inventory_union<Id: Eq + Clone, I: Item<Id = Id>, II: ItemInstance<I>, S: Slot<I, II>>(
+    a: &[S],
+    b: &[S],
+) -> Vec<(Id, u32)> {
+    let mut union = quantities_by_id(a);
+    for (id, quant) in quantities_by_id(b) {
+        match union.iter_mut().find(|(other, _)| *other == id) {
+            Some((_, other)) => *other = quant.max(*other),
+            None => union.push((id, quant)),
+        }
+    }
+    union
+}
+
+/// Gets how much more of each item `a` has than `b`, leaving out items `b` has at least as many of.
+///
+/// ```
+/// # use game_inventory::samples::{DefaultSlot, TORCH, JUNK, TORCH_INST, TORCH_FULL_STACK_INST, JUNK_INST, SWORD_INST};
+/// # use game_inventory::traits::{Slot, Item};
+/// # use game_inventory::helpers::inventory_complement;
+/// let a = vec![DefaultSlot::new(TORCH_FULL_STACK_INST.clone()), DefaultSlot::new(JUNK_INST.clone())];
+/// let b = vec![DefaultSlot::new(SWORD_INST.clone()), DefaultSlot::new(TORCH_INST.clone())];
+/// assert_eq!(inventory_complement(&a, &b), vec![(TORCH.id(), 77), (JUNK.id(), 91)]);
+/// assert!(inventory_complement(&b, &a).iter().all(|(id, _)| *id == "sword"));
+/// ```
+pub fn inventory_complement<
+    Id: Eq + Clone,
+    I: Item<Id = Id>,
+    II: ItemInstance<I>,
+    S: Slot<I, II>,
+>(
+    a: &[S],
+    b: &[S],
+) -> Vec<(Id, u32)> {
+    let b = quantities_by_id(b);
+    quantities_by_id(a)
+        .into_iter()
+        .filter_map(|(id, quant)| {
+            let other = b
+                .iter()
+                .find(|(other, _)| *other == id)
+                .map_or(0, |(_, q)| *q);
+            (quant > other).then(|| (id, quant - other))
+        })
+        .collect()
+}
+
+/// The quantity of each item in an inventory, in the order they first appear.
+///
+/// Added up as `u32` like `item_total_quantity`, so large inventories do not overflow.
+fn quantities_by_id<Id: Eq, I: Item<Id = Id>, II: ItemInstance<I>, S: Slot<I, II>>(
+    inventory: &[S],
+) -> Vec<(Id, u32)> {
+    let mut quantities: Vec<(Id, u32)> = Vec::new();
+    for ii in inventory.iter().filter_map(|s| s.item_instance()) {
+        let item = ii.item();
+        let quant = if item.stackable() {
+            ii.quant() as u32
+        } else {
+            1
+        };
+        match quantities.iter_mut().find(|(id, _)| *id == item.id()) {
+            Some((_, total)) => *total += quant,
+            None => quantities.push((item.id(), quant)),
+        }
+    }
+    quantities
+}
+
+/// Checks if an inventory has all the ingredients for a recipe.
+///
+/// ```
+/// # use game_inventory::samples::{DefaultSlot, TORCH_RECIPE, JUNK_INST, TORCH_INST};
+/// # use game_inventory::traits::Slot;
+/// # use game_inventory::helpers::can_craft;
+/// let inventory = vec![DefaultSlot::new(JUNK_INST.clone())];
+/// assert!(can_craft(&inventory, &TORCH_RECIPE));
+/// let inventory = vec![DefaultSlot::new(TORCH_INST.clone())];
+/// assert!(!can_craft(&inventory, &TORCH_RECIPE));
+/// ```
+pub fn can_craft<
+    Id: Eq + Clone,
+    I: Item<Id = Id>,
+    II: ItemInstance<I>,
+    S: Slot<I, II>,
+    C: Craftable<Output = I>,
+>(
+    inventory: &[S],
+    recipe: &C,
+) -> bool {
+    inventory_difference(inventory, &recipe.ingredients()).is_empty()
+}
+
+/// How much of a required item an inventory has, see `compare_inventory_to_requirements`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct IngredientStatus<Id> {
+    pub item_id: Id,
+    pub required: u16,
+    /// Counted with `item_total_quantity`, so unstackable items count as `1` each.
+    pub have: u32,
+    pub satisfied: bool,
+}
+
+/// Compares an inventory to a list of required items, for showing things like "3/5 cheese" in a crafting or quest UI.
+///
+/// The requirements are in the same form as `Craftable::ingredients`.
+/// ```
+/// # use game_inventory::samples::{DefaultSlot, TORCH_RECIPE, JUNK_INST};
+/// # use game_inventory::traits::Craftable;
+/// # use game_inventory::helpers::{all_requirements_met, compare_inventory_to_requirements};
+/// let inventory = vec![DefaultSlot::new(JUNK_INST.clone())];
+/// let comparison = compare_inventory_to_requirements(&inventory, &TORCH_RECIPE.ingredients());
+/// assert_eq!((comparison[0].have, comparison[0].required), (91, 10));
+/// assert!(all_requirements_met(&comparison));
+///
+/// let comparison = compare_inventory_to_requirements(&inventory, &[("junk", 5), ("sword", 1)]);
+/// assert!(comparison[0].satisfied);
+/// assert_eq!(comparison[1].have, 0);
+/// assert!(!all_requirements_met(&comparison));
+/// ```
+pub fn compare_inventory_to_requirements<
+    Id: Eq + Clone,
+    I: Item<Id = Id>,
+    II: ItemInstance<I>,
+    S: Slot<I, II>,
+>(
+    inventory: &[S],
+    requirements: &[(Id, u16)],
+) -> Vec<IngredientStatus<Id>> {
+    requirements
+        .iter()
+        .map(|(id, required)| {
+            let have = item_total_quantity(inventory, id.clone());
+            IngredientStatus {
+                item_id: id.clone(),
+                required: *required,
+                have,
+                satisfied: have >= *required as u32,
+            }
+        })
+        .collect()
+}
+
+/// Checks if every requirement in a comparison from `compare_inventory_to_requirements` is satisfied.
+pub fn all_requirements_met<Id>(comparison: &[IngredientStatus<Id>]) -> bool {
+    comparison.iter().all(|status| status.satisfied)
+}
+
+/// Gets every recipe that can be crafted with the items in an inventory, for listing them in a crafting UI.
+///
+/// The inventory is only counted once with `item_type_counts`, no matter how many recipes there are.
+/// ```
+/// # use game_inventory::samples::{DefaultSlot, DefaultRecipe, TORCH, SWORD, TORCH_RECIPE, JUNK_INST};
+/// # use game_inventory::helpers::craftable_recipes;
+/// let sword_recipe = DefaultRecipe { ingredients: &[("junk", 200)], output: &SWORD, output_quantity: 1 };
+/// let recipes = [TORCH_RECIPE.clone(), sword_recipe];
+/// let inventory = vec![DefaultSlot::new(JUNK_INST.clone())];
+/// let craftable = craftable_recipes(&inventory, &recipes);
+/// assert_eq!(craftable.len(), 1);
+/// assert_eq!(craftable[0].output.name, TORCH.name);
+/// ```
+pub fn craftable_recipes<
+    'a,
+    Id: Eq + Hash,
+    I: Item<Id = Id>,
+    II: ItemInstance<I>,
+    S: Slot<I, II>,
+    C: Craftable<Output = I>,
+>(
+    inventory: &[S],
+    recipes: &'a [C],
+) -> Vec<&'a C> {
+    let counts = item_type_counts(inventory);
+    recipes
+        .iter()
+        .filter(|recipe| times_craftable(&counts, *recipe) > 0)
+        .collect()
+}
+
+/// Gets how many times a recipe can be crafted with the items in an inventory, for "craft all" buttons.
+///
+/// A recipe without any ingredients can be crafted `u32::MAX` times.
+/// ```
+/// # use game_inventory::samples::{DefaultSlot, TORCH_RECIPE, JUNK_INST, TORCH_INST};
+/// # use game_inventory::helpers::max_craftable_count;
+/// let inventory = vec![DefaultSlot::new(JUNK_INST.clone()), DefaultSlot::new(JUNK_INST.clone())];
+/// assert_eq!(max_craftable_count(&inventory, &TORCH_RECIPE), 18);
+/// let inventory = vec![DefaultSlot::new(TORCH_INST.clone())];
+/// assert_eq!(max_craftable_count(&inventory, &TORCH_RECIPE), 0);
+/// ```
+pub fn max_craftable_count<
+    Id: Eq + Hash,
+    I: Item<Id = Id>,
+    II: ItemInstance<I>,
+    S: Slot<I, II>,
+    C: Craftable<Output = I>,
+>(
+    inventory: &[S],
+    recipe: &C,
+) -> u32 {
+    times_craftable(&item_type_counts(inventory), recipe)
+}
+
+fn times_craftable<Id: Eq + Hash, I: Item<Id = Id>, C: Craftable<Output = I>>(
+    counts: &HashMap<Id, u32>,
+    recipe: &C,
+) -> u32 {
+    let mut required: HashMap<Id, u32> = HashMap::new();
+    for (id, quant) in recipe.ingredients() {
+        *required.entry(id).or_insert(0) += quant as u32;
+    }
+    required
+        .iter()
+        .filter(|(_, quant)| **quant > 0)
+        .map(|(id, quant)| counts.get(id).copied().unwrap_or(0) / quant)
+        .min()
+        .unwrap_or(u32::MAX)
+}
+
+/// Crafts an item, taking the ingredients from `inventory` and putting the result in `output_inventory`.
+///
+/// Either both inventories are changed or neither of them are.
+/// ```
+/// # use game_inventory::samples::{DefaultSlot, TORCH, JUNK, TORCH_RECIPE, JUNK_INST, SWORD_INST};
+/// # use game_inventory::traits::{Slot, Item};
+/// # use game_inventory::helpers::{craft_item, quant_in_inventory};
+/// # use game_inventory::errors::CraftError;
+/// let mut inventory = vec![DefaultSlot::new(JUNK_INST.clone())];
+/// let mut output = vec![DefaultSlot::new(None)];
+/// craft_item(&mut inventory, &TORCH_RECIPE, &mut output).unwrap();
+/// assert_eq!(quant_in_inventory(&inventory, JUNK.id()), 81);
+/// assert_eq!(quant_in_inventory(&output, TORCH.id()), 4);
+///
+/// let mut full = vec![DefaultSlot::new(SWORD_INST.clone())];
+/// assert_eq!(craft_item(&mut inventory, &TORCH_RECIPE, &mut full), Err(CraftError::OutputInventoryFull));
+/// assert_eq!(quant_in_inventory(&inventory, JUNK.id()), 81);
+/// ```
+pub fn craft_item<
+    Id: Eq + Clone,
+    I: Item<Id = Id>,
+    II: ItemInstance<I> + Clone,
+    S: Slot<I, II>,
+    C: Craftable<Output = I>,
+>(
+    inventory: &mut [S],
+    recipe: &C,
+    output_inventory: &mut [S],
+) -> Result<(), CraftError> {
+    if !can_craft(inventory, recipe) {
+        return Err(CraftError::InsufficientIngredients);
+    }
+    let inventory_snapshot = snapshot_inventory(inventory);
+    let output_snapshot = snapshot_inventory(output_inventory);
+
+    for (id, quant) in recipe.ingredients() {
+        let item = inventory
+            .iter()
+            .filter_map(|s| s.item_instance())
+            .find(|i| i.item().id() == id)
+            .map(|i| i.item());
+        let removed = match item {
+            Some(item) => remove_from_inventory(inventory, II::new(item, quant)).is_none(),
+            None => quant == 0,
+        };
+        if !removed {
+            restore_inventory(inventory, inventory_snapshot);
+            return Err(CraftError::InsufficientIngredients);
+        }
+    }
+
+    let (item, quant) = recipe.output();
+    if add_to_inventory(output_inventory, II::new(Arc::new(item), quant)).is_some() {
+        restore_inventory(inventory, inventory_snapshot);
+        restore_inventory(output_inventory, output_snapshot);
+        return Err(CraftError::OutputInventoryFull);
+    }
+    Ok(())
+}
+
+/// Removes several items from an inventory at once, returning the removed instances.
+///
+/// Either every item is removed or none of them are. Unlike calling `remove_from_inventory`
+/// for each item, the inventory is left untouched if any of them are missing.
+/// ```
+/// # use game_inventory::samples::{DefaultSlot, TORCH, JUNK, SWORD, TORCH_INST, JUNK_INST};
+/// # use game_inventory::traits::{Slot, Item, ItemInstance};
+/// # use game_inventory::helpers::{batch_remove_from_inventory, quant_in_inventory};
+/// # use game_inventory::errors::BatchRemoveError;
+/// # use std::sync::Arc;
+/// let mut inventory = vec![DefaultSlot::new(TORCH_INST.clone()), DefaultSlot::new(JUNK_INST.clone())];
+/// let removed = batch_remove_from_inventory(
+///     &mut inventory,
+///     &[(Arc::new(TORCH.clone()), 20), (Arc::new(JUNK.clone()), 90)],
+/// )
+/// .unwrap();
+/// assert_eq!(removed[1].quant(), 90);
+/// assert_eq!(quant_in_inventory(&inventory, TORCH.id()), 3);
+///
+/// let res = batch_remove_from_inventory(
+///     &mut inventory,
+///     &[(Arc::new(TORCH.clone()), 3), (Arc::new(SWORD.clone()), 1)],
+/// );
+/// assert_eq!(res.unwrap_err(), BatchRemoveError::InsufficientStock("sword"));
+/// assert_eq!(quant_in_inventory(&inventory, TORCH.id()), 3);
+/// ```
+pub fn batch_remove_from_inventory<
+    Id: Eq + Clone,
+    I: Item<Id = Id>,
+    II: ItemInstance<I>,
+    S: Slot<I, II>,
+>(
+    inventory: &mut [S],
+    items: &[(Arc<I>, u16)],
+) -> Result<Vec<II>, BatchRemoveError<Id>> {
+    let snapshot = snapshot_inventory(inventory);
+    let mut removed = Vec::with_capacity(items.len());
+    for (item, quant) in items {
+        if remove_from_inventory(inventory, II::new(item.clone(), *quant)).is_some() {
+            restore_inventory(inventory, snapshot);
+            return Err(BatchRemoveError::InsufficientStock(item.id()));
+        }
+        removed.push(II::new(item.clone(), *quant));
+    }
+    Ok(removed)
+}
+
+fn snapshot_inventory<Id: Eq, I: Item<Id = Id>, II: ItemInstance<I>, S: Slot<I, II>>(
+    inventory: &[S],
+) -> Vec<Option<II>> {
+    inventory.iter().map(|s| s.item_instance()).collect()
+}
+
+fn restore_inventory<Id: Eq, I: Item<Id = Id>, II: ItemInstance<I>, S: Slot<I, II>>(
+    inventory: &mut [S],
+    snapshot: Vec<Option<II>>,
+) {
+    for (slot, item_instance) in inventory.iter_mut().zip(snapshot) {
+        slot.set_item_instance(&item_instance);
+    }
+}
+
+/// Checks if `quantity` is in the `quantity_range` of an item.
+///
+/// ```
+/// # use game_inventory::samples::{TORCH, SWORD};
+/// # use game_inventory::helpers::is_valid_quantity;
+/// assert!(is_valid_quantity(&TORCH, 50));
+/// assert!(!is_valid_quantity(&TORCH, 0));
+/// assert!(!is_valid_quantity(&TORCH, 101));
+/// assert!(is_valid_quantity(&SWORD, 0));
+/// ```
+pub fn is_valid_quantity(item: &impl Item, quantity: u16) -> bool {
+    item.quantity_range().contains(&quantity)
+}
+
+/// Checks that no slot in an inventory holds more of a stackable item than its `capacity`.
+///
+/// ```
+/// # use game_inventory::samples::{DefaultSlot, DefaultItemInstance, TORCH, TORCH_INST, SWORD_INST};
+/// # use game_inventory::traits::{Slot, ItemInstance};
+/// # use game_inventory::helpers::validate_inventory;
+/// # use game_inventory::errors::{InventoryError, QuantityError};
+/// # use std::sync::Arc;
+/// let mut inventory = vec![DefaultSlot::new(TORCH_INST.clone()), DefaultSlot::new(SWORD_INST.clone())];
+/// assert!(validate_inventory(&inventory).is_ok());
+/// inventory[0].capacity_override = Some(10);
+/// assert_eq!(
+///     validate_inventory(&inventory),
+///     Err(InventoryError::Quantity(QuantityError::ExceedsMaxQuantity { quantity: 23, max_quant: 10 }))
+/// );
+/// ```
+pub fn validate_inventory<Id: Eq, I: Item<Id = Id>, II: ItemInstance<I>, S: Slot<I, II>>(
+    inventory: &[S],
+) -> Result<(), InventoryError> {
+    match inventory.iter().find_map(validate_slot) {
+        Some(err) => Err(err),
+        None => Ok(()),
+    }
+}
+
+fn validate_slot<Id: Eq, I: Item<Id = Id>, II: ItemInstance<I>, S: Slot<I, II>>(
+    slot: &S,
+) -> Option<InventoryError> {
+    let ii = slot.item_instance()?;
+    if ii.item().stackable() && ii.quant() > slot.capacity() {
+        return Some(
+            QuantityError::ExceedsMaxQuantity {
+                quantity: ii.quant(),
+                max_quant: slot.capacity(),
+            }
+            .into(),
+        );
+    }
+    None
+}
+
+/// Runs every check in `validate_inventory` on each slot, and `verify_no_duplicate_non_stackables`.
+///
+/// Use this instead of `validate_inventory` for games where each unstackable item is unique.
+/// Every problem found is returned, so an empty list means the inventory is valid.
+/// ```
+/// # use game_inventory::samples::{DefaultSlot, TORCH_INST, SWORD_INST};
+/// # use game_inventory::traits::Slot;
+/// # use game_inventory::helpers::validate_inventory_strict;
+/// # use game_inventory::errors::{DuplicateError, ValidationError};
+/// let mut inventory = vec![DefaultSlot::new(SWORD_INST.clone()), DefaultSlot::new(TORCH_INST.clone())];
+/// assert!(validate_inventory_strict(&inventory).is_empty());
+/// inventory.push(DefaultSlot::new(SWORD_INST.clone()));
+/// assert_eq!(
+///     validate_inventory_strict(&inventory),
+///     vec![ValidationError::Duplicate(DuplicateError { item_id: "sword", slot_indices: vec![0, 2] })]
+/// );
+/// ```
+pub fn validate_inventory_strict<Id: Eq, I: Item<Id = Id>, II: ItemInstance<I>, S: Slot<I, II>>(
+    inventory: &[S],
+) -> Vec<ValidationError<Id>> {
+    let mut errors: Vec<ValidationError<Id>> = inventory
+        .iter()
+        .enumerate()
+        .filter_map(|(index, slot)| {
+            validate_slot(slot).map(|error| ValidationError::Slot { index, error })
+        })
+        .collect();
+    errors.extend(
+        verify_no_duplicate_non_stackables(inventory)
+            .into_iter()
+            .map(ValidationError::from),
+    );
+    errors
+}
+
+/// Finds unstackable items that are in more than one slot.
+///
+/// For games where each unstackable item is unique, like a legendary sword, this catches
+/// the same item being duplicated. `validate_inventory` does not run it, since having several
+/// of the same unstackable item is normal in most games, but `validate_inventory_strict` does.
+/// ```
+/// # use game_inventory::samples::{DefaultSlot, TORCH_INST, SWORD_INST};
+/// # use game_inventory::traits::Slot;
+/// # use game_inventory::helpers::verify_no_duplicate_non_stackables;
+/// # use game_inventory::errors::DuplicateError;
+/// let mut inventory = vec![
+///     DefaultSlot::new(SWORD_INST.clone()),
+///     DefaultSlot::new(TORCH_INST.clone()),
+///     DefaultSlot::new(TORCH_INST.clone()),
+/// ];
+/// assert!(verify_no_duplicate_non_stackables(&inventory).is_empty());
+/// inventory.push(DefaultSlot::new(SWORD_INST.clone()));
+/// assert_eq!(
+///     verify_no_duplicate_non_stackables(&inventory),
+///     vec![DuplicateError { item_id: "sword", slot_indices: vec![0, 3] }]
+/// );
+/// ```
+pub fn verify_no_duplicate_non_stackables<
+    Id: Eq,
+    I: Item<Id = Id>,
+    II: ItemInstance<I>,
+    S: Slot<I, II>,
+>(
+    inventory: &[S],
+) -> Vec<DuplicateError<Id>> {
+    let mut found: Vec<DuplicateError<Id>> = Vec::new();
+    for (index, slot) in inventory.iter().enumerate() {
+        let item = match slot.item_instance() {
+            Some(ii) if !ii.item().stackable() => ii.item(),
+            _ => continue,
+        };
+        match found.iter_mut().find(|d| d.item_id == item.id()) {
+            Some(d) => d.slot_indices.push(index),
+            None => found.push(DuplicateError {
+                item_id: item.id(),
+                slot_indices: vec![index],
+            }),
+        }
+    }
+    found.retain(|d| d.slot_indices.len() > 1);
+    found
+}
+
+/// Gets the indices of every slot holding a stack of the item that is not full.
+///
+/// The slots with the most space left come first. Stacks are full when they reach the slot's `capacity`.
+/// ```
+/// # use game_inventory::samples::{DefaultSlot, DefaultItemInstance, TORCH, TORCH_INST, TORCH_FULL_STACK_INST, JUNK_INST};
+/// # use game_inventory::traits::{Slot, Item, ItemInstance};
+/// # use game_inventory::helpers::find_partial_stack_slots;
+/// # use std::sync::Arc;
+/// let inventory = vec![
+///     DefaultSlot::new(Some(DefaultItemInstance::new(Arc::new(TORCH.clone()), 80).unwrap())),
+///     DefaultSlot::new(TORCH_FULL_STACK_INST.clone()),
+///     DefaultSlot::new(JUNK_INST.clone()),
+///     DefaultSlot::new(TORCH_INST.clone()),
+///     DefaultSlot::new(None),
+/// ];
+/// assert_eq!(find_partial_stack_slots(&inventory, TORCH.id()), vec![3, 0]);
+/// ```
+pub fn find_partial_stack_slots<Id: Eq, I: Item<Id = Id>, II: ItemInstance<I>, S: Slot<I, II>>(
+    inventory: &[S],
+    id: Id,
+) -> Vec<usize> {
+    let mut res: Vec<(usize, u16)> = inventory
+        .iter()
+        .enumerate()
+        .filter_map(|(index, slot)| {
+            let ii = slot.item_instance()?;
+            let space = slot.capacity().saturating_sub(ii.quant());
+            (ii.item().stackable() && ii.item().id() == id && space > 0).then_some((index, space))
+        })
+        .collect();
+    res.sort_by_key(|&(_, space)| Reverse(space));
+    res.into_iter().map(|(index, _)| index).collect()
+}
+
+/// Gets the indices of every slot holding a full stack of the item.
+///
+/// Unstackable items are never counted as a full stack.
+/// ```
+/// # use game_inventory::samples::{DefaultSlot, TORCH, SWORD, TORCH_INST, TORCH_FULL_STACK_INST, SWORD_INST};
+/// # use game_inventory::traits::{Slot, Item};
+/// # use game_inventory::helpers::find_full_stack_slots;
+/// let inventory = vec![
+///     DefaultSlot::new(TORCH_FULL_STACK_INST.clone()),
+///     DefaultSlot::new(TORCH_INST.clone()),
+///     DefaultSlot::new(SWORD_INST.clone()),
+///     DefaultSlot::new(TORCH_FULL_STACK_INST.clone()),
+/// ];
+/// assert_eq!(find_full_stack_slots(&inventory, TORCH.id()), vec![0, 3]);
+/// assert!(find_full_stack_slots(&inventory, SWORD.id()).is_empty());
+/// ```
+pub fn find_full_stack_slots<Id: Eq, I: Item<Id = Id>, II: ItemInstance<I>, S: Slot<I, II>>(
+    inventory: &[S],
+    id: Id,
+) -> Vec<usize> {
+    inventory
+        .iter()
+        .enumerate()
+        .filter(|(_, slot)| match slot.item_instance() {
+            Some(ii) => {
+                ii.item().stackable() && ii.item().id() == id && ii.quant() >= slot.capacity()
+            }
+            None => false,
+        })
+        .map(|(index, _)| index)
+        .collect()
+}
+
+/// Formats an inventory as a table with one line per slot, for logging during development.
+///
+/// Each line has the slot index, item id and `stack_progress_text`.
+/// ```
+/// # use game_inventory::samples::{DefaultSlot, TORCH_INST, SWORD_INST};
+/// # use game_inventory::traits::Slot;
+/// # use game_inventory::helpers::debug_print_inventory;
+/// let inventory = vec![
+///     DefaultSlot::new(TORCH_INST.clone()),
+///     DefaultSlot::new(None),
+///     DefaultSlot::new(SWORD_INST.clone()),
+/// ];
+/// assert_eq!(
+///     debug_print_inventory(&inventory),
+///     "   0 torch 23/100\n   1 [empty]\n   2 sword 1/1"
+/// );
+/// ```
+pub fn debug_print_inventory<
+    Id: Eq + Display,
+    I: Item<Id = Id>,
+    II: ItemInstance<I>,
+    S: Slot<I, II>,
+>(
+    inventory: &[S],
+) -> String {
+    debug_print_slots(inventory.iter().enumerate())
+}
+
+/// Like `debug_print_inventory`, but leaves out the empty slots.
+///
+/// ```
+/// # use game_inventory::samples::{DefaultSlot, TORCH_INST, SWORD_INST};
+/// # use game_inventory::traits::Slot;
+/// # use game_inventory::helpers::debug_print_inventory_compact;
+/// let inventory = vec![
+///     DefaultSlot::new(TORCH_INST.clone()),
+///     DefaultSlot::new(None),
+///     DefaultSlot::new(SWORD_INST.clone()),
+/// ];
+/// assert_eq!(debug_print_inventory_compact(&inventory), "   0 torch 23/100\n   2 sword 1/1");
+/// ```
+pub fn debug_print_inventory_compact<
+    Id: Eq + Display,
+    I: Item<Id = Id>,
+    II: ItemInstance<I>,
+    S: Slot<I, II>,
+>(
+    inventory: &[S],
+) -> String {
+    debug_print_slots(occupied(inventory).into_iter())
+}
+
+fn debug_print_slots<
+    'a,
+    Id: Eq + Display,
+    I: Item<Id = Id>,
+    II: ItemInstance<I>,
+    S: Slot<I, II> + 'a,
+>(
+    slots: impl Iterator<Item = (usize, &'a S)>,
+) -> String {
+    let lines: Vec<(usize, Option<(String, String)>)> = slots
+        .map(|(index, s)| {
+            let line = s
+                .item_instance()
+                .map(|ii| (ii.item().id().to_string(), s.stack_progress_text()));
+            (index, line)
+        })
+        .collect();
+    let width = lines
+        .iter()
+        .filter_map(|(_, l)| l.as_ref().map(|(id, _)| id.len()))
+        .max()
+        .unwrap_or(0);
+    lines
+        .into_iter()
+        .map(|(index, line)| match line {
+            Some((id, progress)) => format!("{:>4} {:<width$} {}", index, id, progress),
+            None => format!("{:>4} [empty]", index),
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+/// Hashes the item ids and quantities of an inventory, for checking if it was changed.
+///
+/// Uses FNV-1a instead of the randomly seeded hasher from the standard library,
+/// so the same inventory always gets the same checksum.
+/// ```
+/// # use game_inventory::samples::{DefaultSlot, TORCH_INST, SWORD_INST};
+/// # use game_inventory::traits::Slot;
+/// # use game_inventory::helpers::{inventory_checksum, verify_checksum};
+/// let mut inventory = vec![DefaultSlot::new(TORCH_INST.clone()), DefaultSlot::new(None)];
+/// let checksum = inventory_checksum(&inventory);
+/// assert!(verify_checksum(&inventory, checksum));
+/// inventory[1].set_item_instance(&SWORD_INST);
+/// assert!(!verify_checksum(&inventory, checksum));
+/// ```
+pub fn inventory_checksum<Id: Eq + Hash, I: Item<Id = Id>, II: ItemInstance<I>, S: Slot<I, II>>(
+    inventory: &[S],
+) -> u64 {
+    let mut hasher = Fnv1aHasher::default();
+    for slot in inventory {
+        match slot.item_instance() {
+            Some(ii) => {
+                hasher.write_u8(1);
+                ii.item().id().hash(&mut hasher);
+                hasher.write_u16(ii.quant());
+            }
+            None => hasher.write_u8(0),
+        }
+    }
+    hasher.finish()
+}
+
+/// Checks that an inventory still has the checksum from `inventory_checksum`.
+pub fn verify_checksum<Id: Eq + Hash, I: Item<Id = Id>, II: ItemInstance<I>, S: Slot<I, II>>(
+    inventory: &[S],
+    expected: u64,
+) -> bool {
+    inventory_checksum(inventory) == expected
+}
+
+struct Fnv1aHasher(u64);
+
+impl Default for Fnv1aHasher {
+    fn default() -> Self {
+        Fnv1aHasher(0xcbf29ce484222325)
+    }
+}
+
+impl Hasher for Fnv1aHasher {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 ^= *byte as u64;
+            self.0 = self.0.wrapping_mul(0x100000001b3);
+        }
+    }
+}
+
+/// What happened to an item instance from `ItemInstance::scale_quantity`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ScaleResult {
+    /// The quantity stayed the same.
+    Unchanged,
+    /// The quantity went down.
+    Reduced { by: u16 },
+    /// The quantity went up.
+    Increased { by: u16 },
+    /// The quantity went to zero.
+    Cleared,
+}
+
+/// Scales the quantity of every stack of an item, see `ItemInstance::scale_quantity`.
+///
+/// Slots whose instance was cleared are emptied.
+/// ```
+/// # use game_inventory::samples::{DefaultSlot, DefaultItemInstance, TORCH, TORCH_FULL_STACK_INST, JUNK_INST};
+/// # use game_inventory::traits::{Slot, Item, ItemInstance};
+/// # use game_inventory::helpers::scale_inventory;
+/// # use std::sync::Arc;
+/// let mut inventory = vec![
+///     DefaultSlot::new(TORCH_FULL_STACK_INST.clone()),
+///     DefaultSlot::new(JUNK_INST.clone()),
+///     DefaultSlot::new(Some(DefaultItemInstance::new(Arc::new(TORCH.clone()), 10).unwrap())),
+/// ];
+/// scale_inventory(&mut inventory, TORCH.id(), 0.8);
+/// assert_eq!(inventory.iter().map(|s| s.quantity()).collect::<Vec<_>>(), vec![80, 91, 8]);
+/// scale_inventory(&mut inventory, TORCH.id(), 0.0);
+/// assert!(inventory[0].item_instance().is_none());
+/// assert!(inventory[2].item_instance().is_none());
+/// ```
+pub fn scale_inventory<Id: Eq, I: Item<Id = Id>, II: ItemInstance<I>, S: Slot<I, II>>(
+    inventory: &mut [S],
+    id: Id,
+    factor: f32,
+) {
+    for slot in inventory.iter_mut() {
+        let mut ii = match slot.item_instance() {
+            Some(ii) if ii.item().id() == id => ii,
+            _ => continue,
+        };
+        match ii.scale_quantity(factor) {
+            ScaleResult::Unchanged => {}
+            ScaleResult::Cleared => slot.set_item_instance(&None),
+            _ => slot.set_item_instance(&Some(ii)),
+        }
+    }
+}
+
+/// Brings the quantity of every item instance in an inventory into its `quantity_range`,
+/// see `ItemInstance::clamp_quantity`.
+///
+/// Only slots whose quantity changes are set again. Used to clean up loaded inventories.
+/// ```
+/// # use game_inventory::samples::{DefaultSlot, DefaultItemInstance, TORCH, SWORD, TORCH_INST};
+/// # use game_inventory::traits::Slot;
+/// # use game_inventory::helpers::clamp_inventory_quantities;
+/// # use std::sync::Arc;
+/// let mut inventory = vec![
+///     DefaultSlot::new(Some(DefaultItemInstance::new_unchecked(Arc::new(TORCH.clone()), 500))),
+///     DefaultSlot::new(Some(DefaultItemInstance::new_unchecked(Arc::new(SWORD.clone()), 3))),
+///     DefaultSlot::new(TORCH_INST.clone()),
+///     DefaultSlot::new(None),
+/// ];
+/// clamp_inventory_quantities(&mut inventory);
+/// assert_eq!(inventory.iter().map(|s| s.quantity()).collect::<Vec<_>>(), vec![100, 0, 23, 0]);
+/// assert!(!inventory[2].modified);
+/// ```
+pub fn clamp_inventory_quantities<Id: Eq, I: Item<Id = Id>, II: ItemInstance<I>, S: Slot<I, II>>(
+    inventory: &mut [S],
+) {
+    for slot in inventory.iter_mut() {
+        let ii = match slot.item_instance() {
+            Some(ii) => ii,
+            None => continue,
+        };
+        let quant = ii.quant();
+        let clamped = ii.clamp_quantity();
+        if clamped.quant() != quant {
+            slot.set_item_instance(&Some(clamped));
+        }
+    }
+}
+
+/// Adds several items to an inventory, returning everything that did not fit.
+///
+/// Stacks that are `stackable_with` each other are combined before being added, so each item only
+/// searches the inventory for partial stacks once. Stackable items are added before
+/// unstackable ones, so if the inventory runs out of room the overflow can be different
+/// from adding the items one at a time.
+///
+/// The overflow has one instance for each stackable item, holding all of it that did not fit,
+/// even if that is more than its `max_quant`. It is only split up past `u16::MAX`.
+/// ```
+/// # use game_inventory::samples::{DefaultSlot, DefaultItemInstance, TORCH, SWORD_INST};
+/// # use game_inventory::traits::{Slot, Item, ItemInstance};
+/// # use game_inventory::helpers::{batch_add_to_inventory, quant_in_inventory};
+/// # use std::sync::Arc;
+/// let mut inventory = vec![DefaultSlot::new(None), DefaultSlot::new(None), DefaultSlot::new(None)];
+/// let torches = |q| DefaultItemInstance::new(Arc::new(TORCH.clone()), q).unwrap();
+/// let overflow = batch_add_to_inventory(
+///     &mut inventory,
+///     vec![torches(80), SWORD_INST.clone().unwrap(), torches(90), SWORD_INST.clone().unwrap()],
+/// );
+/// assert_eq!(quant_in_inventory(&inventory, TORCH.id()), 170);
+/// assert_eq!(overflow.len(), 1);
+/// assert_eq!(overflow[0].item().id(), "sword");
+/// ```
+pub fn batch_add_to_inventory<
+    Id: Eq,
+    I: Item<Id = Id>,
+    II: ItemInstance<I> + Clone,
+    S: Slot<I, II>,
+>(
+    inventory: &mut [S],
+    items: Vec<II>,
+) -> Vec<II> {
+    let mut stacks: Vec<(Arc<I>, u32)> = Vec::new();
+    let mut unstackable = Vec::new();
+    for ii in items {
+        let item = ii.item();
+        if !item.stackable() {
+            unstackable.push(ii);
+            continue;
+        }
+        match stacks.iter_mut().find(|(i, _)| i.stackable_with(&ii)) {
+            Some((_, quant)) => *quant += ii.quant() as u32,
+            None => stacks.push((item, ii.quant() as u32)),
+        }
+    }
+
+    let mut overflow = Vec::new();
+    for (item, mut quant) in stacks {
+        let max_quant = item.max_quant().max(1) as u32;
+        let mut left = 0;
+        while quant > 0 {
+            let chunk = quant.min(max_quant);
+            quant -= chunk;
+            if let Some(res) = add_to_inventory(inventory, II::new(item.clone(), chunk as u16)) {
+                // Nothing more of this item fits, so the rest is overflow too.
+                left += res.quant() as u32 + quant;
+                break;
+            }
+        }
+        while left > 0 {
+            let chunk = left.min(u16::MAX as u32);
+            left -= chunk;
+            overflow.push(II::new(item.clone(), chunk as u16));
+        }
+    }
+    for ii in unstackable {
+        if let Some(res) = add_to_inventory(inventory, ii) {
+            overflow.push(res);
+        }
+    }
+    overflow
+}
+
+/// A single slot that changed, see `InventoryDiff`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SlotDiff<II> {
+    pub index: usize,
+    pub before: Option<II>,
+    pub after: Option<II>,
+}
+
+/// The slots that changed between two states of an inventory, for undoing the changes with `apply_reverse`.
+///
+/// ```
+/// # use game_inventory::samples::{DefaultSlot, TORCH_INST, JUNK_INST};
+/// # use game_inventory::traits::{Slot, ItemInstance};
+/// # use game_inventory::helpers::{add_to_inventory, apply_reverse, InventoryDiff};
+/// let mut inventory = vec![DefaultSlot::new(TORCH_INST.clone()), DefaultSlot::new(None)];
+/// let mut undo_stack = Vec::new();
+///
+/// let before = inventory.clone();
+/// add_to_inventory(&mut inventory, JUNK_INST.clone().unwrap());
+/// undo_stack.push(InventoryDiff::between(&before, &inventory));
+/// assert_eq!(undo_stack[0].changes.len(), 1);
+///
+/// apply_reverse(&mut inventory, &undo_stack.pop().unwrap()).unwrap();
+/// assert!(inventory[1].item_instance().is_none());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct InventoryDiff<II> {
+    pub changes: Vec<SlotDiff<II>>,
+}
+
+impl<II> InventoryDiff<II> {
+    /// Compares two states of an inventory slot by slot, using `ItemInstance::is_same_as`.
+    ///
+    /// Slots are matched by index, so both should have the same number of slots.
+    pub fn between<Id: Eq, I: Item<Id = Id>, S: Slot<I, II>>(before: &[S], after: &[S]) -> Self
+    where
+        II: ItemInstance<I>,
+    {
+        let changes = before
+            .iter()
+            .zip(after)
+            .enumerate()
+            .filter_map(|(index, (b, a))| {
+                let (before, after) = (b.item_instance(), a.item_instance());
+                (!same_item_instance(&before, &after)).then_some(SlotDiff {
+                    index,
+                    before,
+                    after,
+                })
+            })
+            .collect();
+        InventoryDiff { changes }
+    }
+
+    /// Checks if nothing changed.
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+}
+
+/// Undoes the changes recorded in `diff`, putting every changed slot back how it was.
+///
+/// Each slot has to still hold what the diff changed it to. If any of them do not,
+/// nothing is changed and the error says which slot it was.
+/// ```
+/// # use game_inventory::samples::{DefaultSlot, TORCH_INST, JUNK_INST};
+/// # use game_inventory::traits::Slot;
+/// # use game_inventory::helpers::{apply_reverse, InventoryDiff};
+/// # use game_inventory::errors::ApplyError;
+/// let before = vec![DefaultSlot::new(TORCH_INST.clone()), DefaultSlot::new(None)];
+/// let mut inventory = vec![DefaultSlot::new(None), DefaultSlot::new(JUNK_INST.clone())];
+/// let diff = InventoryDiff::between(&before, &inventory);
+///
+/// inventory[1].set_item_instance(&None);
+/// assert_eq!(apply_reverse(&mut inventory, &diff), Err(ApplyError::SlotChanged(1)));
+/// assert!(inventory[0].item_instance().is_none());
+/// ```
+pub fn apply_reverse<Id: Eq, I: Item<Id = Id>, II: ItemInstance<I>, S: Slot<I, II>>(
+    inventory: &mut [S],
+    diff: &InventoryDiff<II>,
+) -> Result<(), ApplyError> {
+    let snapshot = snapshot_inventory(inventory);
+    for change in diff.changes.iter().rev() {
+        let res = match inventory.get_mut(change.index) {
+            None => Err(ApplyError::IndexOutOfBounds(change.index)),
+            Some(slot) if !same_item_instance(&slot.item_instance(), &change.after) => {
+                Err(ApplyError::SlotChanged(change.index))
+            }
+            Some(slot) => {
+                slot.set_item_instance(&change.before);
+                Ok(())
+            }
+        };
+        if res.is_err() {
+            restore_inventory(inventory, snapshot);
+            return res;
+        }
+    }
+    Ok(())
+}
+
+fn same_item_instance<I: Item, II: ItemInstance<I>>(a: &Option<II>, b: &Option<II>) -> bool {
+    match (a, b) {
+        (None, None) => true,
+        (Some(a), Some(b)) => a.is_same_as(b),
+        _ => false,
+    }
+}
+
+/// A new state for one slot, for sending changes to an inventory over the network.
+///
+/// The write side of `InventoryDiff`, a `SlotDiff` can be turned into the change it made.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SlotChange<II> {
+    pub index: usize,
+    pub new_state: Option<II>,
+}
+
+impl<II> From<SlotDiff<II>> for SlotChange<II> {
+    fn from(diff: SlotDiff<II>) -> Self {
+        SlotChange {
+            index: diff.index,
+            new_state: diff.after,
+        }
+    }
+}
+
+/// Sets the slot at `change.index` to `change.new_state`.
+///
+/// ```
+/// # use game_inventory::samples::{DefaultSlot, TORCH_INST};
+/// # use game_inventory::traits::Slot;
+/// # use game_inventory::helpers::{apply_slot_change, SlotChange};
+/// # use game_inventory::errors::InventoryError;
+/// let mut inventory = vec![DefaultSlot::new(None)];
+/// let change = SlotChange { index: 0, new_state: TORCH_INST.clone() };
+/// apply_slot_change(&mut inventory, &change).unwrap();
+/// assert_eq!(inventory[0].quantity(), 23);
+///
+/// let change = SlotChange { index: 1, new_state: None };
+/// assert_eq!(apply_slot_change(&mut inventory, &change), Err(InventoryError::IndexOutOfBounds(1)));
+/// ```
+pub fn apply_slot_change<Id: Eq, I: Item<Id = Id>, II: ItemInstance<I>, S: Slot<I, II>>(
+    inventory: &mut [S],
+    change: &SlotChange<II>,
+) -> Result<(), InventoryError> {
+    let slot = inventory
+        .get_mut(change.index)
+        .ok_or(InventoryError::IndexOutOfBounds(change.index))?;
+    slot.set_item_instance(&change.new_state);
+    Ok(())
+}
+
+/// Applies several `SlotChange`s in order, like a patch received from a server.
+///
+/// All indices are checked first, so nothing is changed if any of them are out of bounds.
+/// ```
+/// # use game_inventory::samples::{DefaultSlot, TORCH_INST, JUNK_INST};
+/// # use game_inventory::traits::Slot;
+/// # use game_inventory::helpers::{add_to_inventory, apply_slot_changes, InventoryDiff, SlotChange};
+/// let mut server = vec![DefaultSlot::new(TORCH_INST.clone()), DefaultSlot::new(None)];
+/// let mut client = server.clone();
+///
+/// let before = server.clone();
+/// add_to_inventory(&mut server, JUNK_INST.clone().unwrap());
+/// let patch: Vec<SlotChange<_>> = InventoryDiff::between(&before, &server)
+///     .changes
+///     .into_iter()
+///     .map(SlotChange::from)
+///     .collect();
+///
+/// apply_slot_changes(&mut client, &patch).unwrap();
+/// assert_eq!(client[1].quantity(), 91);
+/// ```
+pub fn apply_slot_changes<Id: Eq, I: Item<Id = Id>, II: ItemInstance<I>, S: Slot<I, II>>(
+    inventory: &mut [S],
+    changes: &[SlotChange<II>],
+) -> Result<(), InventoryError> {
+    if let Some(change) = changes.iter().find(|c| c.index >= inventory.len()) {
+        return Err(InventoryError::IndexOutOfBounds(change.index));
+    }
+    for change in changes {
+        apply_slot_change(inventory, change)?;
+    }
+    Ok(())
 }
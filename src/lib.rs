@@ -20,6 +20,22 @@
 //! - `trait ItemInstance` DefaultItem data that changes between instances, like enchantments, how many you have, their durability, e.t.c.
 //! - `trait Slot` Manages a single item instance. Good for binding user action to different types of instance modification (stack splitting, stack combining, e.t.c.). Allows for binding to the UI via a callback function.
 //! - `Vec<Slot>` Is the way an inventory is composed. There are builtin functions in `inventory_management` that can help manage the inventory.
+//! - `inventory` Has inventory types like `FixedInventory` that can be used in place of a `Vec<Slot>`.
+//! - `serialization` Has the `InventorySerializer` trait for saving and loading inventories.
+//! - `capture` Has `InventoryCapture` for recording and replaying changes to an inventory.
+//! - `equipment` Has `EquipmentSet` for the items a character has equipped.
+//! - `observer` Has `InventoryObserver` and `InventoryBroadcast` for sending slot events to several parts of a game.
+//! - `pool` Has `InventoryPool` for reusing item instances that are created and dropped often.
+//!
+//! ## Features
+//!
+//! - `capture` (on by default) Records the changes made through `InventoryCapture`.
+//! - `serde` Derives `Serialize` and `Deserialize` for the sample structs, errors and other data types.
+//! - `json` Adds `JsonInventorySerializer` and `load_inventory_from_json`, which use `serde_json`.
+//!   `JsonInventorySerializer` can write camelCase field names for web game backends.
+//! - `bincode` Adds `BinaryInventorySerializer` and `load_inventory_binary`, which use `bincode`.
+//! - `tooltip` Adds `Item::tooltip_lines` and the `display` module for building structured tooltips.
+//! - `rand` Adds `quantity_weighted_random_slot`, which uses `rand`.
 //!
 //! ## Basic example
 //!
@@ -55,27 +71,34 @@
 //! }
 //! // start using it in combination with everything else!
 //! let CHEESE: DefaultItem = DefaultItem{name:"Cheese", max_quantity:100, image:None, item_type:"Food"};
-//! let CHEESE_INST: Option<DefaultItemInstance<DefaultItem>> = Some(DefaultItemInstance{item:Arc::new(CHEESE.clone()), quantity:32});
 //! let SWORD: DefaultItem = DefaultItem{name:"Sword", max_quantity:0, image:None, item_type:"Weapon"};
-//! let SWORD_INST: Option<DefaultItemInstance<DefaultItem>> = Some(DefaultItemInstance{item:Arc::new(SWORD.clone()), quantity:0});
-//! let mut inventory = vec![
-//!     DefaultSlot::new(CHEESE_INST.clone()),
+//! let mut inventory: Vec<DefaultSlot<DefaultItem, DefaultItemInstance<DefaultItem>>> = vec![
+//!     DefaultSlot::full_stack(Arc::new(CHEESE.clone())),
 //!     DefaultSlot::new(None),
 //!     DefaultSlot::new(None),
-//!     DefaultSlot::new(CHEESE_INST.clone())
+//!     DefaultSlot::single(Arc::new(CHEESE.clone())),
 //! ];
-//! add_to_inventory(&mut inventory, SWORD_INST.unwrap());
+//! add_to_inventory(&mut inventory, DefaultItemInstance::new(Arc::new(SWORD.clone()), 0).unwrap());
 //! assert_eq!(inventory[0].item_instance.as_ref().unwrap().item().id(), CHEESE.id());
-//! assert_eq!(inventory[0].item_instance.as_ref().unwrap().quant(), CHEESE_INST.as_ref().unwrap().quant());
+//! assert_eq!(inventory[0].item_instance.as_ref().unwrap().quant(), CHEESE.max_quant());
 //! assert_eq!(inventory[1].item_instance.as_ref().unwrap().item().id(), SWORD.id());
 //! assert!(inventory[2].item_instance.is_none());
 //! assert_eq!(inventory[3].item_instance.as_ref().unwrap().item().id(), CHEESE.id());
-//! assert_eq!(inventory[3].item_instance.as_ref().unwrap().quant(), CHEESE_INST.as_ref().unwrap().quant());
+//! assert_eq!(inventory[3].item_instance.as_ref().unwrap().quant(), 1);
 //! ```
 
+pub mod capture;
+#[cfg(feature = "tooltip")]
+pub mod display;
+pub mod equipment;
+pub mod errors;
+pub mod inventory;
 pub mod inventory_management;
+pub mod observer;
+pub mod pool;
 pub mod sample_items;
 pub mod sample_structs;
+pub mod serialization;
 pub mod slot_management;
 pub mod traits;
 
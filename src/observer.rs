@@ -0,0 +1,99 @@
+//! Observing the events emitted by slots, see `Slot::emit_event`.
+use crate::slot_management::SlotEvent;
+
+/// Something that wants to know about the events emitted by a slot, like a UI widget or a save system.
+///
+/// Closures that take a `&SlotEvent` are observers too.
+pub trait InventoryObserver<II> {
+    /// Called with every event the slot emits.
+    fn notify(&mut self, event: &SlotEvent<II>);
+}
+
+impl<II, F: FnMut(&SlotEvent<II>)> InventoryObserver<II> for F {
+    fn notify(&mut self, event: &SlotEvent<II>) {
+        self(event)
+    }
+}
+
+/// An observer that forwards every event to several other observers, in the order they were added.
+///
+/// ```
+/// # use game_inventory::observer::InventoryBroadcast;
+/// # use game_inventory::samples::{DefaultSlot, DefaultItem, DefaultItemInstance, TORCH_INST};
+/// # use game_inventory::slot_management::SlotEvent;
+/// # use game_inventory::traits::Slot;
+/// # use std::cell::Cell;
+/// let (ui, saves) = (Cell::new(0), Cell::new(0));
+/// let mut broadcast = InventoryBroadcast::new();
+/// broadcast.add_observer(Box::new(|_: &SlotEvent<_>| ui.set(ui.get() + 1)));
+/// broadcast.add_observer(Box::new(|_: &SlotEvent<_>| saves.set(saves.get() + 1)));
+///
+/// let mut slot: DefaultSlot<DefaultItem, DefaultItemInstance<DefaultItem>> =
+///     DefaultSlot::new(TORCH_INST.clone());
+/// slot.set_broadcast(broadcast);
+/// slot.emit_event(SlotEvent::Selected);
+/// assert_eq!((ui.get(), saves.get()), (1, 1));
+/// ```
+pub struct InventoryBroadcast<'a, II> {
+    observers: Vec<(usize, Box<dyn InventoryObserver<II> + 'a>)>,
+    next_id: usize,
+}
+
+impl<'a, II> InventoryBroadcast<'a, II> {
+    /// Creates a broadcast with no observers.
+    pub fn new() -> Self {
+        InventoryBroadcast {
+            observers: Vec::new(),
+            next_id: 0,
+        }
+    }
+
+    /// Adds an observer, returning the id to remove it with.
+    pub fn add_observer(&mut self, observer: Box<dyn InventoryObserver<II> + 'a>) -> usize {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.observers.push((id, observer));
+        id
+    }
+
+    /// Removes the observer with the given id, returning `false` if there was none.
+    /// ```
+    /// # use game_inventory::observer::InventoryBroadcast;
+    /// # use game_inventory::samples::{DefaultItem, DefaultItemInstance};
+    /// # use game_inventory::slot_management::SlotEvent;
+    /// let mut broadcast: InventoryBroadcast<DefaultItemInstance<DefaultItem>> = InventoryBroadcast::new();
+    /// let id = broadcast.add_observer(Box::new(|_: &SlotEvent<_>| {}));
+    /// assert!(broadcast.remove_observer(id));
+    /// assert!(!broadcast.remove_observer(id));
+    /// assert!(broadcast.is_empty());
+    /// ```
+    pub fn remove_observer(&mut self, id: usize) -> bool {
+        let len = self.observers.len();
+        self.observers.retain(|(i, _)| *i != id);
+        self.observers.len() != len
+    }
+
+    /// The number of observers.
+    pub fn len(&self) -> usize {
+        self.observers.len()
+    }
+
+    /// Checks if there are no observers.
+    pub fn is_empty(&self) -> bool {
+        self.observers.is_empty()
+    }
+}
+
+impl<'a, II> Default for InventoryBroadcast<'a, II> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a, II> InventoryObserver<II> for InventoryBroadcast<'a, II> {
+    fn notify(&mut self, event: &SlotEvent<II>) {
+        for (_, observer) in &mut self.observers {
+            observer.notify(event);
+        }
+    }
+}
@@ -0,0 +1,151 @@
+//! A pool for reusing item instances that are created and dropped often.
+use std::{
+    cell::RefCell,
+    collections::VecDeque,
+    ops::{Deref, DerefMut},
+    rc::{Rc, Weak},
+    sync::Arc,
+};
+
+use crate::errors::QuantityError;
+use crate::sample_structs::{check_quantity, DefaultItemInstance};
+use crate::traits::Item;
+
+/// Hands out instances of a single item, taking them back once they are dropped.
+///
+/// Starts with `capacity` instances ready to use and grows if they run out.
+/// ```
+/// # use game_inventory::pool::InventoryPool;
+/// # use game_inventory::samples::TORCH;
+/// # use game_inventory::traits::ItemInstance;
+/// # use std::sync::Arc;
+/// let mut pool = InventoryPool::new(Arc::new(TORCH.clone()), 2);
+/// let a = pool.acquire(10).unwrap();
+/// let b = pool.acquire(20).unwrap();
+/// let c = pool.acquire(30).unwrap();
+/// assert_eq!(pool.available(), 0);
+/// assert_eq!(c.quant(), 30);
+/// drop((a, b, c));
+/// assert_eq!(pool.available(), 3);
+/// ```
+#[derive(Debug)]
+pub struct InventoryPool<I: Item> {
+    item: Arc<I>,
+    free: Rc<RefCell<VecDeque<DefaultItemInstance<I>>>>,
+}
+
+impl<I: Item> InventoryPool<I> {
+    /// Creates a pool of `capacity` instances of `item`.
+    pub fn new(item: Arc<I>, capacity: usize) -> Self {
+        let free = (0..capacity)
+            .map(|_| DefaultItemInstance {
+                item: item.clone(),
+                quantity: 0,
+            })
+            .collect();
+        InventoryPool {
+            item,
+            free: Rc::new(RefCell::new(free)),
+        }
+    }
+
+    /// Takes an instance with the given quantity out of the pool.
+    ///
+    /// A new instance is created if the pool is empty. The quantity is checked against the
+    /// items `quantity_range` like `DefaultItemInstance::new`, nothing is taken from the pool if it is invalid.
+    /// ```
+    /// # use game_inventory::pool::InventoryPool;
+    /// # use game_inventory::samples::{TORCH, SWORD};
+    /// # use game_inventory::errors::QuantityError;
+    /// # use std::sync::Arc;
+    /// let mut torches = InventoryPool::new(Arc::new(TORCH.clone()), 1);
+    /// assert_eq!(
+    ///     torches.acquire(500).unwrap_err(),
+    ///     QuantityError::ExceedsMaxQuantity { quantity: 500, max_quant: 100 }
+    /// );
+    /// assert_eq!(torches.available(), 1);
+    /// let mut swords = InventoryPool::new(Arc::new(SWORD.clone()), 1);
+    /// assert!(swords.acquire(1).is_err());
+    /// assert!(swords.acquire(0).is_ok());
+    /// ```
+    pub fn acquire(&mut self, quantity: u16) -> Result<PooledInstance<I>, QuantityError> {
+        check_quantity(&*self.item, quantity)?;
+        let mut instance =
+            self.free
+                .borrow_mut()
+                .pop_front()
+                .unwrap_or_else(|| DefaultItemInstance {
+                    item: self.item.clone(),
+                    quantity: 0,
+                });
+        instance.quantity = quantity;
+        Ok(PooledInstance {
+            instance: Some(instance),
+            pool: Rc::downgrade(&self.free),
+        })
+    }
+
+    /// The number of instances waiting to be acquired.
+    pub fn available(&self) -> usize {
+        self.free.borrow().len()
+    }
+}
+
+/// An item instance from an `InventoryPool`, which goes back to the pool when dropped.
+///
+/// If the pool was dropped first, the instance is dropped along with it.
+/// ```
+/// # use game_inventory::pool::InventoryPool;
+/// # use game_inventory::samples::TORCH;
+/// # use game_inventory::traits::ItemInstance;
+/// # use std::sync::Arc;
+/// let mut pool = InventoryPool::new(Arc::new(TORCH.clone()), 1);
+/// let inst = pool.acquire(10).unwrap();
+/// drop(pool);
+/// assert_eq!(inst.quant(), 10);
+/// ```
+#[derive(Debug)]
+pub struct PooledInstance<I: Item> {
+    instance: Option<DefaultItemInstance<I>>,
+    pool: Weak<RefCell<VecDeque<DefaultItemInstance<I>>>>,
+}
+
+impl<I: Item> PooledInstance<I> {
+    /// Takes the instance out without returning it to the pool,
+    /// for when it needs to be stored in a slot.
+    /// ```
+    /// # use game_inventory::pool::InventoryPool;
+    /// # use game_inventory::samples::TORCH;
+    /// # use game_inventory::traits::ItemInstance;
+    /// # use std::sync::Arc;
+    /// let mut pool = InventoryPool::new(Arc::new(TORCH.clone()), 1);
+    /// let inst = pool.acquire(10).unwrap().into_inner();
+    /// assert_eq!(pool.available(), 0);
+    /// assert_eq!(inst.quant(), 10);
+    /// ```
+    pub fn into_inner(mut self) -> DefaultItemInstance<I> {
+        self.instance.take().unwrap()
+    }
+}
+
+impl<I: Item> Deref for PooledInstance<I> {
+    type Target = DefaultItemInstance<I>;
+
+    fn deref(&self) -> &Self::Target {
+        self.instance.as_ref().unwrap()
+    }
+}
+
+impl<I: Item> DerefMut for PooledInstance<I> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.instance.as_mut().unwrap()
+    }
+}
+
+impl<I: Item> Drop for PooledInstance<I> {
+    fn drop(&mut self) {
+        if let (Some(instance), Some(pool)) = (self.instance.take(), self.pool.upgrade()) {
+            pool.borrow_mut().push_back(instance);
+        }
+    }
+}
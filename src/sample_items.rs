@@ -7,26 +7,24 @@
 
 use std::sync::Arc;
 
-use crate::sample_structs::{DefaultItem, DefaultItemInstance};
+use crate::sample_structs::{DefaultItem, DefaultItemInstance, DefaultRecipe};
 
 // Define simple stackable items as static, not const
 
 /// A simple stackable item.
-pub static TORCH: DefaultItem = DefaultItem {
-    name: "torch",
-    max_quantity: 100,
-};
+pub static TORCH: DefaultItem = DefaultItem::new("torch", 100, "tool");
 
 /// A simple stackable item.
-pub static JUNK: DefaultItem = DefaultItem {
-    name: "junk",
-    max_quantity: 100,
-};
+pub static JUNK: DefaultItem = DefaultItem::new("junk", 100, "junk");
 
 /// A simple unstackable item.
-pub static SWORD: DefaultItem = DefaultItem {
-    name: "sword",
-    max_quantity: 0,
+pub static SWORD: DefaultItem = DefaultItem::new("sword", 0, "weapon");
+
+/// A recipe that turns ten junk into four torches.
+pub static TORCH_RECIPE: DefaultRecipe = DefaultRecipe {
+    ingredients: &[("junk", 10)],
+    output: &TORCH,
+    output_quantity: 4,
 };
 
 lazy_static::lazy_static! {
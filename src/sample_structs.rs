@@ -1,7 +1,18 @@
 //! A collection of sample structs used for testing the system, and showing how it can be used.
-use std::{fmt::Debug, marker::PhantomData, sync::Arc};
+use std::{
+    borrow::Cow,
+    fmt::{Debug, Display},
+    marker::PhantomData,
+    sync::Arc,
+};
 
-use crate::traits::{Item, ItemInstance, Slot};
+#[cfg(feature = "tooltip")]
+use crate::display::{TooltipLine, TooltipStyle};
+use crate::errors::{EmptySlotError, InventoryError, QuantityError};
+use crate::inventory::InventoryIndex;
+use crate::observer::{InventoryBroadcast, InventoryObserver};
+use crate::slot_management::SlotEvent;
+use crate::traits::{Craftable, Item, ItemCategory, ItemInstance, ItemValue, ItemWeight, Slot};
 
 /// A sample item struct used for testing.
 ///
@@ -10,9 +21,169 @@ use crate::traits::{Item, ItemInstance, Slot};
 /// As long as your implementation satisfies the trait bounds it does not matter what immutable
 /// item data you put in here.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct DefaultItem<'a> {
     pub name: &'a str,
     pub max_quantity: u16,
+    pub item_type: &'a str,
+    #[cfg_attr(
+        feature = "serde",
+        serde(borrow, default, skip_serializing_if = "Option::is_none")
+    )]
+    pub description: Option<&'a str>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(borrow, default, skip_serializing_if = "Option::is_none")
+    )]
+    pub flavor_text: Option<&'a str>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub icon_id: Option<u32>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(borrow, default, skip_serializing_if = "Option::is_none")
+    )]
+    pub icon_path: Option<&'a str>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(borrow, default, skip_serializing_if = "has_no_tags")
+    )]
+    pub tags: Cow<'a, [&'a str]>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "is_weightless")
+    )]
+    pub weight: f32,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "is_worthless")
+    )]
+    pub value: u64,
+}
+
+#[cfg(feature = "serde")]
+fn has_no_tags(tags: &[&str]) -> bool {
+    tags.is_empty()
+}
+
+#[cfg(feature = "serde")]
+fn is_weightless(weight: &f32) -> bool {
+    *weight == 0.0
+}
+
+#[cfg(feature = "serde")]
+fn is_worthless(value: &u64) -> bool {
+    *value == 0
+}
+
+impl<'a> DefaultItem<'a> {
+    /// Creates an item with no description, flavor text or icon, that weighs and is worth nothing.
+    ///
+    /// ```
+    /// # use game_inventory::samples::DefaultItem;
+    /// # use game_inventory::traits::Item;
+    /// let cheese = DefaultItem::new("cheese", 100, "food")
+    ///     .with_description("Restores a small amount of health.")
+    ///     .with_flavor_text("Aged for a hundred years.");
+    /// assert_eq!(cheese.description(), Some("Restores a small amount of health."));
+    /// assert_eq!(cheese.flavor_text(), Some("Aged for a hundred years."));
+    /// assert!(DefaultItem::new("rock", 10, "junk").description().is_none());
+    /// ```
+    pub const fn new(name: &'a str, max_quantity: u16, item_type: &'a str) -> Self {
+        DefaultItem {
+            name,
+            max_quantity,
+            item_type,
+            description: None,
+            flavor_text: None,
+            icon_id: None,
+            icon_path: None,
+            tags: Cow::Borrowed(&[]),
+            weight: 0.0,
+            value: 0,
+        }
+    }
+
+    /// Sets the description shown in tooltips.
+    pub fn with_description(self, description: &'a str) -> Self {
+        DefaultItem {
+            description: Some(description),
+            ..self
+        }
+    }
+
+    /// Sets the flavor text shown in tooltips.
+    pub fn with_flavor_text(self, flavor_text: &'a str) -> Self {
+        DefaultItem {
+            flavor_text: Some(flavor_text),
+            ..self
+        }
+    }
+
+    /// Sets the id of the items sprite in a texture atlas.
+    ///
+    /// ```
+    /// # use game_inventory::samples::DefaultItem;
+    /// # use game_inventory::traits::Item;
+    /// let cheese = DefaultItem::new("cheese", 100, "food")
+    ///     .with_icon_id(12)
+    ///     .with_icon_path("icons/cheese.png");
+    /// assert_eq!(cheese.icon_id(), Some(12));
+    /// assert_eq!(cheese.icon_path(), Some("icons/cheese.png"));
+    /// ```
+    pub fn with_icon_id(self, icon_id: u32) -> Self {
+        DefaultItem {
+            icon_id: Some(icon_id),
+            ..self
+        }
+    }
+
+    /// Sets the path to the items icon.
+    pub fn with_icon_path(self, icon_path: &'a str) -> Self {
+        DefaultItem {
+            icon_path: Some(icon_path),
+            ..self
+        }
+    }
+
+    /// Sets the weight of a single one of the item.
+    ///
+    /// ```
+    /// # use game_inventory::samples::DefaultItem;
+    /// # use game_inventory::traits::ItemWeight;
+    /// assert_eq!(DefaultItem::new("anvil", 1, "junk").with_weight(50.0).weight(), 50.0);
+    /// ```
+    pub fn with_weight(self, weight: f32) -> Self {
+        DefaultItem { weight, ..self }
+    }
+
+    /// Sets the value of a single one of the item.
+    ///
+    /// ```
+    /// # use game_inventory::samples::DefaultItem;
+    /// # use game_inventory::traits::ItemValue;
+    /// assert_eq!(DefaultItem::new("gem", 10, "treasure").with_value(250).value(), 250);
+    /// ```
+    pub fn with_value(self, value: u64) -> Self {
+        DefaultItem { value, ..self }
+    }
+
+    /// Sets the tags of the item, replacing any it already had.
+    ///
+    /// ```
+    /// # use game_inventory::samples::DefaultItem;
+    /// # use game_inventory::traits::Item;
+    /// let potion = DefaultItem::new("fire potion", 10, "potion").with_tags(&["fire", "consumable"]);
+    /// assert_eq!(potion.tags(), &["fire", "consumable"]);
+    /// ```
+    pub fn with_tags(self, tags: &'a [&'a str]) -> Self {
+        DefaultItem {
+            tags: Cow::Borrowed(tags),
+            ..self
+        }
+    }
 }
 
 impl<'a> Item for DefaultItem<'a> {
@@ -28,6 +199,98 @@ impl<'a> Item for DefaultItem<'a> {
     fn id(&self) -> &'a str {
         self.name
     }
+
+    fn description(&self) -> Option<&str> {
+        self.description
+    }
+
+    fn flavor_text(&self) -> Option<&str> {
+        self.flavor_text
+    }
+
+    fn icon_id(&self) -> Option<u32> {
+        self.icon_id
+    }
+
+    fn icon_path(&self) -> Option<&str> {
+        self.icon_path
+    }
+
+    fn tags(&self) -> &[&str] {
+        &self.tags
+    }
+
+    /// The name, the stack size if the item is stackable, then the description and flavor text.
+    /// ```
+    /// # use game_inventory::samples::{DefaultItem, SWORD};
+    /// # use game_inventory::traits::Item;
+    /// # use game_inventory::display::{TooltipLine, TooltipStyle};
+    /// let cheese = DefaultItem::new("cheese", 100, "food").with_flavor_text("Smells strong.");
+    /// assert_eq!(
+    ///     cheese.tooltip_lines(),
+    ///     vec![
+    ///         TooltipLine::new("cheese", TooltipStyle::Header),
+    ///         TooltipLine::new("Stacks up to 100", TooltipStyle::Stat),
+    ///         TooltipLine::new("Smells strong.", TooltipStyle::FlavorText),
+    ///     ]
+    /// );
+    /// assert_eq!(SWORD.tooltip_lines().len(), 1);
+    /// ```
+    #[cfg(feature = "tooltip")]
+    fn tooltip_lines(&self) -> Vec<TooltipLine> {
+        let mut lines = vec![TooltipLine::new(self.name, TooltipStyle::Header)];
+        if self.stackable() {
+            lines.push(TooltipLine::new(
+                format!("Stacks up to {}", self.max_quantity),
+                TooltipStyle::Stat,
+            ));
+        }
+        if let Some(description) = self.description {
+            lines.push(TooltipLine::new(description, TooltipStyle::Description));
+        }
+        if let Some(flavor_text) = self.flavor_text {
+            lines.push(TooltipLine::new(flavor_text, TooltipStyle::FlavorText));
+        }
+        lines
+    }
+}
+
+impl<'a> ItemCategory for DefaultItem<'a> {
+    fn category(&self) -> &str {
+        self.item_type
+    }
+}
+
+impl<'a> ItemWeight for DefaultItem<'a> {
+    fn weight(&self) -> f32 {
+        self.weight
+    }
+}
+
+impl<'a> ItemValue for DefaultItem<'a> {
+    fn value(&self) -> u64 {
+        self.value
+    }
+}
+
+/// A sample crafting recipe used for testing.
+#[derive(Debug, Clone)]
+pub struct DefaultRecipe<'a> {
+    pub ingredients: &'a [(&'a str, u16)],
+    pub output: &'a DefaultItem<'a>,
+    pub output_quantity: u16,
+}
+
+impl<'a> Craftable for DefaultRecipe<'a> {
+    type Output = DefaultItem<'a>;
+
+    fn ingredients(&self) -> Vec<(&'a str, u16)> {
+        self.ingredients.to_vec()
+    }
+
+    fn output(&self) -> (DefaultItem<'a>, u16) {
+        (self.output.clone(), self.output_quantity)
+    }
 }
 
 /// A sample item instance struct used for testing.
@@ -36,13 +299,290 @@ impl<'a> Item for DefaultItem<'a> {
 ///
 /// As long as your implementation satisfies the trait bounds it does not matter what instanced
 /// item data you put in here.
+///
+/// The fields are private so that the quantity is always valid for the item, see `DefaultItemInstance::new`.
+/// `ItemInstance::new` does not check the quantity, since the inventory functions also use
+/// instances to ask for a quantity of an item, like how many to remove.
+/// Deserializing checks the quantity the same way `DefaultItemInstance::new` does.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(
+        try_from = "RawItemInstance<I>",
+        bound(deserialize = "I: serde::Deserialize<'de>")
+    )
+)]
 pub struct DefaultItemInstance<I: Item> {
-    pub item: Arc<I>,
-    pub quantity: u16,
+    pub(crate) item: Arc<I>,
+    pub(crate) quantity: u16,
+}
+
+/// The fields of a `DefaultItemInstance` before its quantity is checked.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct RawItemInstance<I> {
+    item: Arc<I>,
+    quantity: u16,
 }
 
-impl<'a, I: Item> ItemInstance<I> for DefaultItemInstance<I> {
+#[cfg(feature = "serde")]
+impl<I: Item> TryFrom<RawItemInstance<I>> for DefaultItemInstance<I> {
+    type Error = QuantityError;
+
+    fn try_from(raw: RawItemInstance<I>) -> Result<Self, QuantityError> {
+        DefaultItemInstance::new(raw.item, raw.quantity)
+    }
+}
+
+pub(crate) fn check_quantity<I: Item>(item: &I, quantity: u16) -> Result<(), QuantityError> {
+    let range = item.quantity_range();
+    if quantity > *range.end() {
+        return Err(QuantityError::ExceedsMaxQuantity {
+            quantity,
+            max_quant: *range.end(),
+        });
+    }
+    if quantity < *range.start() {
+        return Err(QuantityError::BelowMinQuantity {
+            quantity,
+            min_quant: *range.start(),
+        });
+    }
+    Ok(())
+}
+
+impl<I: Item> DefaultItemInstance<I> {
+    /// Creates an instance of `item`, checking that the quantity is valid.
+    ///
+    /// The quantity has to be in the items `quantity_range`, so by default stackable items
+    /// hold from `1` up to their max quantity and unstackable items always have a quantity of `0`.
+    /// ```
+    /// # use game_inventory::samples::{DefaultItemInstance, TORCH, SWORD};
+    /// # use game_inventory::traits::ItemInstance;
+    /// # use game_inventory::errors::QuantityError;
+    /// # use std::sync::Arc;
+    /// let torches = DefaultItemInstance::new(Arc::new(TORCH.clone()), 100).unwrap();
+    /// assert_eq!(torches.quant(), 100);
+    /// assert!(DefaultItemInstance::new(Arc::new(TORCH.clone()), 101).is_err());
+    /// assert_eq!(
+    ///     DefaultItemInstance::new(Arc::new(TORCH.clone()), 0).unwrap_err(),
+    ///     QuantityError::BelowMinQuantity { quantity: 0, min_quant: 1 }
+    /// );
+    /// assert!(DefaultItemInstance::new(Arc::new(SWORD.clone()), 0).is_ok());
+    /// assert_eq!(
+    ///     DefaultItemInstance::new(Arc::new(SWORD.clone()), 1).unwrap_err(),
+    ///     QuantityError::ExceedsMaxQuantity { quantity: 1, max_quant: 0 }
+    /// );
+    /// ```
+    pub fn new(item: Arc<I>, quantity: u16) -> Result<Self, QuantityError> {
+        check_quantity(&*item, quantity)?;
+        Ok(DefaultItemInstance { item, quantity })
+    }
+
+    /// Creates an instance of `item` without checking the quantity.
+    ///
+    /// ```
+    /// # use game_inventory::samples::{DefaultItemInstance, TORCH};
+    /// # use game_inventory::traits::ItemInstance;
+    /// # use std::sync::Arc;
+    /// let torches = DefaultItemInstance::new_unchecked(Arc::new(TORCH.clone()), 123);
+    /// assert_eq!(torches.quant(), 123);
+    /// ```
+    pub fn new_unchecked(item: Arc<I>, quantity: u16) -> Self {
+        DefaultItemInstance { item, quantity }
+    }
+
+    /// Creates a full stack of `item`.
+    ///
+    /// Unstackable items have a quantity of `0`.
+    /// ```
+    /// # use game_inventory::samples::{DefaultItemInstance, TORCH, SWORD};
+    /// # use game_inventory::traits::ItemInstance;
+    /// # use std::sync::Arc;
+    /// assert_eq!(DefaultItemInstance::full_stack(Arc::new(TORCH.clone())).quant(), 100);
+    /// assert_eq!(DefaultItemInstance::full_stack(Arc::new(SWORD.clone())).quant(), 0);
+    /// ```
+    pub fn full_stack(item: Arc<I>) -> Self {
+        let quantity = match item.stackable() {
+            true => item.max_quant(),
+            false => 0,
+        };
+        DefaultItemInstance { item, quantity }
+    }
+
+    /// Creates an instance holding one of `item`.
+    ///
+    /// Unstackable items have a quantity of `0`, which already means a single item.
+    /// ```
+    /// # use game_inventory::samples::{DefaultItemInstance, TORCH, SWORD};
+    /// # use game_inventory::traits::ItemInstance;
+    /// # use std::sync::Arc;
+    /// assert_eq!(DefaultItemInstance::single(Arc::new(TORCH.clone())).quant(), 1);
+    /// assert_eq!(DefaultItemInstance::single(Arc::new(SWORD.clone())).quant(), 0);
+    /// ```
+    pub fn single(item: Arc<I>) -> Self {
+        let quantity = match item.stackable() {
+            true => 1,
+            false => 0,
+        };
+        DefaultItemInstance { item, quantity }
+    }
+
+    /// Returns the instance with its quantity replaced.
+    ///
+    /// ```
+    /// # use game_inventory::samples::TORCH_INST;
+    /// # use game_inventory::traits::ItemInstance;
+    /// let torches = TORCH_INST.clone().unwrap().with_quantity(10).unwrap();
+    /// assert_eq!(torches.quant(), 10);
+    /// ```
+    /// The quantity has to be in the items `quantity_range`.
+    /// ```
+    /// # use game_inventory::samples::TORCH_INST;
+    /// assert!(TORCH_INST.clone().unwrap().with_quantity(101).is_err());
+    /// assert!(TORCH_INST.clone().unwrap().with_quantity(0).is_err());
+    /// ```
+    pub fn with_quantity(mut self, quantity: u16) -> Result<Self, QuantityError> {
+        check_quantity(&*self.item, quantity)?;
+        self.quantity = quantity;
+        Ok(self)
+    }
+
+    /// Returns the instance with its item replaced, keeping the quantity.
+    ///
+    /// The quantity has to be in the new items `quantity_range`.
+    /// ```
+    /// # use game_inventory::samples::{TORCH_INST, JUNK, SWORD};
+    /// # use game_inventory::traits::{Item, ItemInstance};
+    /// # use std::sync::Arc;
+    /// let junk = TORCH_INST.clone().unwrap().with_item(Arc::new(JUNK.clone())).unwrap();
+    /// assert_eq!(junk.item().id(), JUNK.id());
+    /// assert_eq!(junk.quant(), 23);
+    /// assert!(junk.with_item(Arc::new(SWORD.clone())).is_err());
+    /// ```
+    pub fn with_item(mut self, item: Arc<I>) -> Result<Self, QuantityError> {
+        check_quantity(&*item, self.quantity)?;
+        self.item = item;
+        Ok(self)
+    }
+
+    /// Returns the instance with its quantity reduced by `amount`.
+    ///
+    /// The quantity left has to be in the items `quantity_range`.
+    /// ```
+    /// # use game_inventory::samples::TORCH_INST;
+    /// # use game_inventory::traits::ItemInstance;
+    /// # use game_inventory::errors::QuantityError;
+    /// let torches = TORCH_INST.clone().unwrap().reduce(3).unwrap();
+    /// assert_eq!(torches.quant(), 20);
+    /// assert_eq!(
+    ///     torches.reduce(20).unwrap_err(),
+    ///     QuantityError::BelowMinQuantity { quantity: 0, min_quant: 1 }
+    /// );
+    /// ```
+    /// You cannot take more items than the instance holds.
+    /// ```
+    /// # use game_inventory::samples::TORCH_INST;
+    /// assert!(TORCH_INST.clone().unwrap().reduce(24).is_err());
+    /// ```
+    pub fn reduce(mut self, amount: u16) -> Result<Self, QuantityError> {
+        if amount > self.quantity {
+            return Err(QuantityError::InsufficientQuantity {
+                quantity: self.quantity,
+                requested: amount,
+            });
+        }
+        check_quantity(&*self.item, self.quantity - amount)?;
+        self.quantity -= amount;
+        Ok(self)
+    }
+
+    /// How much the quantity changed going from `other` to `self`.
+    ///
+    /// A negative value means the quantity went down.
+    /// ```
+    /// # use game_inventory::samples::{TORCH_INST, TORCH_FULL_STACK_INST};
+    /// let before = TORCH_FULL_STACK_INST.clone().unwrap();
+    /// let after = TORCH_INST.clone().unwrap();
+    /// assert_eq!(after.quantity_delta(&before), -77);
+    /// assert_eq!(before.quantity_delta(&after), 77);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If the two instances are of different items.
+    /// ```should_panic
+    /// # use game_inventory::samples::{TORCH_INST, JUNK_INST};
+    /// TORCH_INST.clone().unwrap().quantity_delta(&JUNK_INST.clone().unwrap());
+    /// ```
+    pub fn quantity_delta(&self, other: &Self) -> i32 {
+        assert!(
+            self.item.id() == other.item.id(),
+            "Cannot get the quantity delta between two different items."
+        );
+        self.quantity as i32 - other.quantity as i32
+    }
+
+    /// Whether the quantity is different between `self` and `other`.
+    ///
+    /// ```
+    /// # use game_inventory::samples::{TORCH_INST, TORCH_FULL_STACK_INST};
+    /// let torches = TORCH_INST.clone().unwrap();
+    /// assert!(!torches.is_quantity_changed(&torches.clone()));
+    /// assert!(torches.is_quantity_changed(&TORCH_FULL_STACK_INST.clone().unwrap()));
+    /// ```
+    pub fn is_quantity_changed(&self, other: &Self) -> bool {
+        self.quantity != other.quantity
+    }
+
+    /// Puts this item instance into a new `DefaultSlot`.
+    ///
+    /// ```
+    /// # use game_inventory::samples::TORCH_INST;
+    /// # use game_inventory::traits::Slot;
+    /// let slot = TORCH_INST.clone().unwrap().reduce(3).unwrap().to_slot();
+    /// assert_eq!(slot.quantity(), 20);
+    /// ```
+    pub fn to_slot<'a>(self) -> DefaultSlot<'a, I, Self>
+    where
+        Self: Clone,
+    {
+        self.into_slot()
+    }
+
+    /// Puts this item instance into the slot at `index`, returning what was there before.
+    ///
+    /// ```
+    /// # use game_inventory::samples::{DefaultSlot, TORCH_INST, JUNK_INST};
+    /// # use game_inventory::traits::{Slot, ItemInstance};
+    /// # use game_inventory::errors::InventoryError;
+    /// let mut inventory = vec![DefaultSlot::new(None), DefaultSlot::new(JUNK_INST.clone())];
+    /// let replaced = TORCH_INST.clone().unwrap().to_slot_at_index(&mut inventory, 1).unwrap();
+    /// assert_eq!(replaced.unwrap().quant(), 91);
+    /// assert_eq!(inventory[1].quantity(), 23);
+    /// assert_eq!(
+    ///     TORCH_INST.clone().unwrap().to_slot_at_index(&mut inventory, 2).unwrap_err(),
+    ///     InventoryError::IndexOutOfBounds(2)
+    /// );
+    /// ```
+    pub fn to_slot_at_index<S: Slot<I, Self>>(
+        self,
+        inventory: &mut [S],
+        index: impl Into<InventoryIndex>,
+    ) -> Result<Option<Self>, InventoryError> {
+        let index = index.into();
+        let slot = match index.checked_get_mut(inventory) {
+            None => return Err(InventoryError::IndexOutOfBounds(index.get())),
+            Some(s) => s,
+        };
+        let replaced = slot.item_instance();
+        slot.set_item_instance(&Some(self));
+        Ok(replaced)
+    }
+}
+
+impl<I: Item> ItemInstance<I> for DefaultItemInstance<I> {
     fn quant(&self) -> u16 {
         self.quantity
     }
@@ -52,10 +592,19 @@ impl<'a, I: Item> ItemInstance<I> for DefaultItemInstance<I> {
     }
 
     fn new(item: Arc<I>, quantity: u16) -> Self {
-        DefaultItemInstance { item, quantity }
+        DefaultItemInstance::new_unchecked(item, quantity)
+    }
+}
+
+/// Two instances are equal if `is_same_as` says they are.
+impl<I: Item> PartialEq for DefaultItemInstance<I> {
+    fn eq(&self, other: &Self) -> bool {
+        self.is_same_as(other)
     }
 }
 
+impl<I: Item> Eq for DefaultItemInstance<I> {}
+
 /// A sample slot struct used for testing.
 ///
 /// A significant reduction in boiler plate would
@@ -65,29 +614,313 @@ impl<'a, I: Item> ItemInstance<I> for DefaultItemInstance<I> {
 ///
 /// The main thing you probably want to change other than that is the transfer method.
 /// some methods like `half_stack_split` and `combine_stack` would be pretty useful.
-pub struct DefaultSlot<'a, I: Item, II: ItemInstance<I>> {
+///
+/// `added_handler` and `removed_handler` are called from `on_added` and `on_removed`,
+/// and `event_handler` from `emit_event`, which is how you would bind the slot to your UI.
+/// Setting the item instance emits `SlotEvent::QuantityChanged` or `SlotEvent::ItemSwapped`.
+///
+/// `capacity_override` limits how many items fit in the slot, regardless of the stored item.
+/// `item_filter` limits which items the slot `accepts`.
+///
+/// `metadata` is for game specific data about the slot, like whether it is pinned or which hotkey
+/// it is bound to. It is `()` by default, so it takes up no space unless it is used.
+/// The metadata type has to implement `Default` for the slot to be a `Slot`, since `Slot::new`
+/// creates slots without being given any metadata.
+/// ```
+/// # use game_inventory::samples::{DefaultSlot, DefaultItem, DefaultItemInstance, TORCH_INST};
+/// # use game_inventory::traits::Slot;
+/// #[derive(Debug, Clone, Default)]
+/// struct SlotMeta {
+///     pinned: bool,
+///     hotkey: Option<char>,
+/// }
+/// let mut slot: DefaultSlot<DefaultItem, DefaultItemInstance<DefaultItem>, SlotMeta> =
+///     DefaultSlot::with_metadata(TORCH_INST.clone(), SlotMeta { pinned: true, hotkey: Some('1') });
+/// slot.set_item_instance(&None);
+/// assert!(slot.metadata.pinned);
+/// assert_eq!(slot.metadata.hotkey, Some('1'));
+/// ```
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(bound(
+        serialize = "II: serde::Serialize, M: serde::Serialize",
+        deserialize = "II: serde::Deserialize<'de>, M: serde::Deserialize<'de> + Default"
+    ))
+)]
+pub struct DefaultSlot<'a, I: Item, II: ItemInstance<I>, M = ()> {
     pub item_instance: Option<II>,
     pub modified: bool,
+    pub capacity_override: Option<u16>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub item_filter: Option<fn(&I) -> bool>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub metadata: M,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub added_handler: Option<SlotHandler<'a, II>>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub removed_handler: Option<SlotHandler<'a, II>>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub event_handler: Option<SlotEventHandler<'a, II>>,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub phantom: PhantomData<&'a I>,
 }
 
-impl<'a, I: Item, II: ItemInstance<I> + Debug> Debug for DefaultSlot<'a, I, II> {
+/// A callback that is given the item instance that was added to or removed from a `DefaultSlot`.
+pub type SlotHandler<'a, II> = Box<dyn FnMut(&II) + 'a>;
+
+/// A callback that is given every `SlotEvent` emitted by a `DefaultSlot`.
+pub type SlotEventHandler<'a, II> = Box<dyn FnMut(SlotEvent<II>) + 'a>;
+
+impl<'a, I: Item, II: ItemInstance<I> + Debug, M: Debug> Debug for DefaultSlot<'a, I, II, M> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("BasicSlot")
             .field("item_instance", &self.item_instance)
             .field("modified", &self.modified)
+            .field("capacity_override", &self.capacity_override)
+            .field("metadata", &self.metadata)
             .finish()
     }
 }
 
-impl<'a, I: Item, II: ItemInstance<I> + Sized + Clone> Slot<I, II> for DefaultSlot<'a, I, II> {
+impl<'a, I: Item, II: ItemInstance<I>> DefaultSlot<'a, I, II> {
+    /// Creates a slot without any metadata, the same as `Slot::new`.
+    ///
+    /// Lets `DefaultSlot::new` be called without the metadata type being written out.
+    pub fn new(item_instance: Option<II>) -> Self {
+        DefaultSlot::with_metadata(item_instance, ())
+    }
+}
+
+impl<'a, I: Item, II: ItemInstance<I>, M> DefaultSlot<'a, I, II, M> {
+    /// Creates a slot with the given metadata.
+    pub fn with_metadata(item_instance: Option<II>, metadata: M) -> Self {
+        DefaultSlot {
+            item_instance,
+            modified: false,
+            capacity_override: None,
+            item_filter: None,
+            metadata,
+            added_handler: None,
+            removed_handler: None,
+            event_handler: None,
+            phantom: PhantomData,
+        }
+    }
+
+    /// Takes the item instance out of the slot, dropping the slot.
+    ///
+    /// No handlers or observers are called, since the slot is gone.
+    /// ```
+    /// # use game_inventory::samples::{DefaultSlot, DefaultItem, DefaultItemInstance, TORCH_INST};
+    /// # use game_inventory::traits::ItemInstance;
+    /// let slot: DefaultSlot<DefaultItem, DefaultItemInstance<DefaultItem>> = DefaultSlot::new(TORCH_INST.clone());
+    /// assert_eq!(slot.into_item_instance().unwrap().quant(), 23);
+    /// ```
+    pub fn into_item_instance(self) -> Option<II> {
+        self.item_instance
+    }
+
+    /// Like `into_item_instance`, but an empty slot is an error, for use with `?`.
+    ///
+    /// ```
+    /// # use game_inventory::samples::{DefaultSlot, DefaultItem, DefaultItemInstance};
+    /// # use game_inventory::errors::EmptySlotError;
+    /// let slot: DefaultSlot<DefaultItem, DefaultItemInstance<DefaultItem>> = DefaultSlot::new(None);
+    /// assert_eq!(slot.into_item_instance_or_empty_err(), Err(EmptySlotError));
+    /// ```
+    pub fn into_item_instance_or_empty_err(self) -> Result<II, EmptySlotError> {
+        self.item_instance.ok_or(EmptySlotError)
+    }
+
+    /// Sends every event emitted by the slot to `observer`, replacing the `event_handler`.
+    pub fn set_observer<O: InventoryObserver<II> + 'a>(&mut self, mut observer: O) {
+        self.event_handler = Some(Box::new(move |event| observer.notify(&event)));
+    }
+
+    /// Sends every event emitted by the slot to all the observers in `broadcast`.
+    pub fn set_broadcast(&mut self, broadcast: InventoryBroadcast<'a, II>)
+    where
+        II: 'a,
+    {
+        self.set_observer(broadcast)
+    }
+}
+
+impl<'a, I: Item, II: ItemInstance<I> + Clone> DefaultSlot<'a, I, II> {
+    /// Creates a slot holding a full stack of the item.
+    ///
+    /// Unstackable items will have a quantity of `0`.
+    /// ```
+    /// # use game_inventory::samples::{DefaultSlot, DefaultItemInstance, DefaultItem, TORCH, SWORD};
+    /// # use game_inventory::traits::Slot;
+    /// # use std::sync::Arc;
+    /// let torches: DefaultSlot<DefaultItem, DefaultItemInstance<DefaultItem>> =
+    ///     DefaultSlot::full_stack(Arc::new(TORCH.clone()));
+    /// assert_eq!(torches.quantity(), 100);
+    /// let sword: DefaultSlot<DefaultItem, DefaultItemInstance<DefaultItem>> =
+    ///     DefaultSlot::full_stack(Arc::new(SWORD.clone()));
+    /// assert_eq!(sword.quantity(), 0);
+    /// ```
+    pub fn full_stack(item: Arc<I>) -> Self {
+        let quantity = if item.stackable() {
+            item.max_quant()
+        } else {
+            0
+        };
+        Self::new(Some(II::new(item, quantity)))
+    }
+
+    /// Creates a slot holding one of the item.
+    ///
+    /// Unstackable items will have a quantity of `0`.
+    /// ```
+    /// # use game_inventory::samples::{DefaultSlot, DefaultItemInstance, DefaultItem, TORCH, SWORD};
+    /// # use game_inventory::traits::Slot;
+    /// # use std::sync::Arc;
+    /// let torch: DefaultSlot<DefaultItem, DefaultItemInstance<DefaultItem>> =
+    ///     DefaultSlot::single(Arc::new(TORCH.clone()));
+    /// assert_eq!(torch.quantity(), 1);
+    /// let sword: DefaultSlot<DefaultItem, DefaultItemInstance<DefaultItem>> =
+    ///     DefaultSlot::single(Arc::new(SWORD.clone()));
+    /// assert_eq!(sword.quantity(), 0);
+    /// ```
+    pub fn single(item: Arc<I>) -> Self {
+        let quantity = if item.stackable() { 1 } else { 0 };
+        Self::new(Some(II::new(item, quantity)))
+    }
+}
+
+impl<'a, I: Item + ItemCategory, II: ItemInstance<I>, M> DefaultSlot<'a, I, II, M>
+where
+    I::Id: Display,
+{
+    /// Describes what is in the slot for tooltips and logs, like `"torch x23 (tool)"`.
+    ///
+    /// Unstackable items leave out the quantity, and empty slots are `"[Empty]"`.
+    /// The category is only shown if the item has one.
+    pub fn display_name(&self) -> String {
+        let ii = match &self.item_instance {
+            None => return "[Empty]".to_owned(),
+            Some(ii) => ii,
+        };
+        let item = ii.item();
+        let mut name = item.id().to_string();
+        if item.stackable() {
+            name = format!("{} x{}", name, ii.quant());
+        }
+        if !item.category().is_empty() {
+            name = format!("{} ({})", name, item.category());
+        }
+        name
+    }
+}
+
+impl<'a, I: Item + ItemCategory, II: ItemInstance<I>, M> Display for DefaultSlot<'a, I, II, M>
+where
+    I::Id: Display,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.display_name())
+    }
+}
+
+/// Handlers cannot be cloned, so the cloned slot has none.
+impl<'a, I: Item, II: ItemInstance<I> + Clone, M: Clone> Clone for DefaultSlot<'a, I, II, M> {
+    fn clone(&self) -> Self {
+        DefaultSlot {
+            item_instance: self.item_instance.clone(),
+            modified: self.modified,
+            capacity_override: self.capacity_override,
+            item_filter: self.item_filter,
+            metadata: self.metadata.clone(),
+            added_handler: None,
+            removed_handler: None,
+            event_handler: None,
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<'a, I: Item, II: ItemInstance<I> + Sized + Clone, M: Default> Slot<I, II>
+    for DefaultSlot<'a, I, II, M>
+{
     fn item_instance(&self) -> Option<II> {
         self.item_instance.clone()
     }
 
+    fn is_empty(&self) -> bool {
+        self.item_instance.is_none()
+    }
+
     fn set_item_instance(&mut self, item_instance: &Option<II>) {
         self.set_modified(true);
-        self.item_instance = item_instance.clone()
+        let old = std::mem::replace(&mut self.item_instance, item_instance.clone());
+        let same_item = match (&old, item_instance) {
+            (Some(old), Some(new)) => old.item().id() == new.item().id(),
+            _ => false,
+        };
+        if !same_item {
+            if let Some(old) = &old {
+                self.on_removed(old);
+            }
+            if let Some(new) = item_instance {
+                self.on_added(new);
+            }
+        }
+        if let (Some(old), Some(new)) = (old, item_instance) {
+            if old.item().id() != new.item().id() {
+                self.emit_event(SlotEvent::ItemSwapped {
+                    old,
+                    new: new.clone(),
+                });
+            } else if old.quant() != new.quant() {
+                self.emit_event(SlotEvent::QuantityChanged {
+                    old: old.quant(),
+                    new: new.quant(),
+                });
+            }
+        }
+    }
+
+    fn capacity(&self) -> u16 {
+        match self.capacity_override {
+            Some(capacity) => capacity,
+            None => self
+                .item_instance
+                .as_ref()
+                .map(|i| i.item().max_quant())
+                .unwrap_or(0),
+        }
+    }
+
+    fn capacity_for(&self, item: &I) -> u16 {
+        self.capacity_override.unwrap_or_else(|| item.max_quant())
+    }
+
+    fn accepts(&self, item: &I) -> bool {
+        match self.item_filter {
+            Some(filter) => filter(item),
+            None => true,
+        }
+    }
+
+    fn emit_event(&mut self, event: SlotEvent<II>) {
+        if let Some(handler) = &mut self.event_handler {
+            handler(event)
+        }
+    }
+
+    fn on_added(&mut self, item_instance: &II) {
+        if let Some(handler) = &mut self.added_handler {
+            handler(item_instance)
+        }
+    }
+
+    fn on_removed(&mut self, item_instance: &II) {
+        if let Some(handler) = &mut self.removed_handler {
+            handler(item_instance)
+        }
     }
 
     fn modified(&mut self) -> bool {
@@ -97,12 +930,158 @@ impl<'a, I: Item, II: ItemInstance<I> + Sized + Clone> Slot<I, II> for DefaultSl
     fn set_modified(&mut self, modified: bool) {
         self.modified = modified
     }
-
     fn new(item_instance: Option<II>) -> Self {
-        DefaultSlot {
+        DefaultSlot::with_metadata(item_instance, M::default())
+    }
+}
+
+/// Builds an inventory where every item instance gets its own slot.
+///
+/// ```
+/// # use game_inventory::samples::{DefaultSlot, DefaultItem, DefaultItemInstance, TORCH_INST, SWORD_INST};
+/// # use game_inventory::traits::{Slot, Item, ItemInstance};
+/// let inventory: Vec<DefaultSlot<DefaultItem, DefaultItemInstance<DefaultItem>>> =
+///     [TORCH_INST.clone().unwrap(), SWORD_INST.clone().unwrap()].into_iter().collect();
+/// assert_eq!(inventory.len(), 2);
+/// assert_eq!(inventory[0].item_id(), Some("torch"));
+/// assert_eq!(inventory[1].item_id(), Some("sword"));
+/// ```
+impl<'a, I: Item + Clone, M: Default> FromIterator<DefaultItemInstance<I>>
+    for Vec<DefaultSlot<'a, I, DefaultItemInstance<I>, M>>
+{
+    fn from_iter<T: IntoIterator<Item = DefaultItemInstance<I>>>(iter: T) -> Self {
+        iter.into_iter()
+            .map(|i| DefaultSlot::with_metadata(Some(i), M::default()))
+            .collect()
+    }
+}
+
+/// Lets an inventory with empty slots be built with `map(DefaultSlot::from)`,
+/// since `collect` cannot be implemented for `Option` items directly.
+///
+/// ```
+/// # use game_inventory::samples::{DefaultSlot, DefaultItem, DefaultItemInstance, TORCH_INST};
+/// # use game_inventory::traits::Slot;
+/// let inventory: Vec<DefaultSlot<DefaultItem, DefaultItemInstance<DefaultItem>>> =
+///     [TORCH_INST.clone(), None].into_iter().map(DefaultSlot::from).collect();
+/// assert_eq!(inventory.len(), 2);
+/// assert_eq!(inventory[0].item_id(), Some("torch"));
+/// assert!(inventory[1].item_instance.is_none());
+/// ```
+impl<'a, I: Item, II: ItemInstance<I> + Clone> From<Option<II>> for DefaultSlot<'a, I, II> {
+    fn from(item_instance: Option<II>) -> Self {
+        DefaultSlot::new(item_instance)
+    }
+}
+
+/// A sample slot with a fixed capacity, for slots that hold more or less than an items max quantity.
+///
+/// A quiver that holds up to 999 arrows would be one of these. `capacity` is used
+/// instead of the items `max_quant`, the same as `capacity_override` on `DefaultSlot`.
+/// Slots made with `Slot::new` can hold up to `u16::MAX` of an item.
+/// ```
+/// # use game_inventory::samples::{ResizableSlot, DefaultItem, DefaultItemInstance, TORCH};
+/// # use game_inventory::traits::{Slot, ItemInstance};
+/// # use game_inventory::helpers::add_to_inventory;
+/// # use std::sync::Arc;
+/// let mut quiver: Vec<ResizableSlot<DefaultItem, DefaultItemInstance<DefaultItem>>> =
+///     vec![ResizableSlot::with_capacity(None, 999)];
+/// let arrows = |q| DefaultItemInstance::new_unchecked(Arc::new(TORCH.clone()), q);
+/// assert!(add_to_inventory(&mut quiver, arrows(600)).is_none());
+/// assert_eq!(add_to_inventory(&mut quiver, arrows(600)).unwrap().quant(), 201);
+/// assert_eq!(quiver[0].quantity(), 999);
+/// assert_eq!(quiver[0].capacity(), 999);
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(bound(
+        serialize = "II: serde::Serialize",
+        deserialize = "II: serde::Deserialize<'de>"
+    ))
+)]
+pub struct ResizableSlot<I: Item, II: ItemInstance<I>> {
+    pub item_instance: Option<II>,
+    pub modified: bool,
+    pub capacity: u16,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub phantom: PhantomData<I>,
+}
+
+impl<I: Item, II: ItemInstance<I>> ResizableSlot<I, II> {
+    /// Creates a slot that holds up to `capacity` of an item.
+    pub fn with_capacity(item_instance: Option<II>, capacity: u16) -> Self {
+        ResizableSlot {
             item_instance,
             modified: false,
+            capacity,
             phantom: PhantomData,
         }
     }
 }
+
+impl<I: Item, II: ItemInstance<I> + Clone> Slot<I, II> for ResizableSlot<I, II> {
+    fn item_instance(&self) -> Option<II> {
+        self.item_instance.clone()
+    }
+
+    fn set_item_instance(&mut self, item_instance: &Option<II>) {
+        self.set_modified(true);
+        self.item_instance = item_instance.clone();
+    }
+
+    fn capacity(&self) -> u16 {
+        self.capacity
+    }
+
+    fn capacity_for(&self, _item: &I) -> u16 {
+        self.capacity
+    }
+
+    fn modified(&mut self) -> bool {
+        self.modified
+    }
+
+    fn set_modified(&mut self, modified: bool) {
+        self.modified = modified
+    }
+    fn new(item_instance: Option<II>) -> Self {
+        ResizableSlot::with_capacity(item_instance, u16::MAX)
+    }
+}
+
+/// An item instance on its own is the simplest slot, for when `DefaultSlot` is more than you need.
+///
+/// There is nowhere to store whether it was modified, so `modified` is always `true`.
+/// ```
+/// # use game_inventory::samples::{DefaultItem, DefaultItemInstance, TORCH_INST};
+/// # use game_inventory::traits::Slot;
+/// # use game_inventory::helpers::add_to_inventory;
+/// let mut inventory: Vec<Option<DefaultItemInstance<DefaultItem>>> = vec![None, None];
+/// assert!(add_to_inventory(&mut inventory, TORCH_INST.clone().unwrap()).is_none());
+/// assert_eq!(inventory[0].quantity(), 23);
+/// assert!(inventory[1].is_empty());
+/// ```
+impl<I: Item, II: ItemInstance<I> + Clone> Slot<I, II> for Option<II> {
+    fn item_instance(&self) -> Option<II> {
+        self.clone()
+    }
+
+    fn set_item_instance(&mut self, item_instance: &Option<II>) {
+        *self = item_instance.clone();
+    }
+
+    fn is_empty(&self) -> bool {
+        self.is_none()
+    }
+
+    fn modified(&mut self) -> bool {
+        true
+    }
+
+    fn set_modified(&mut self, _modified: bool) {}
+    fn new(item_instance: Option<II>) -> Self {
+        item_instance
+    }
+}
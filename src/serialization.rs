@@ -0,0 +1,260 @@
+//! Saving and loading inventories, independent of the format they are stored in.
+//!
+//! Implement `InventorySerializer` for your own format, or enable the `json` or `bincode`
+//! features for the built in ones.
+use crate::errors::DeserializeError;
+
+/// Converts an inventory to and from some stored format.
+pub trait InventorySerializer {
+    /// The type of inventory that is stored.
+    type Inventory;
+    /// What the inventory is stored as, like a `String` or bytes.
+    type Output;
+    /// Stores the inventory.
+    fn serialize(&self, inventory: &Self::Inventory) -> Self::Output;
+    /// Reads back an inventory that was stored by `serialize`.
+    fn deserialize(&self, data: &Self::Output) -> Result<Self::Inventory, DeserializeError>;
+}
+
+#[cfg(feature = "json")]
+pub use json::{load_inventory_from_json, JsonInventorySerializer, KeyCase};
+
+#[cfg(feature = "json")]
+mod json {
+    use std::marker::PhantomData;
+
+    use serde::{de::DeserializeOwned, Serialize};
+    use serde_json::Value;
+
+    use super::InventorySerializer;
+    use crate::errors::DeserializeError;
+    use crate::inventory_management::clamp_inventory_quantities;
+    use crate::traits::{Item, ItemInstance, Slot};
+
+    /// Stores an inventory as a JSON string.
+    ///
+    /// Use `load_inventory_from_json` to load slots, which also cleans up their quantities.
+    /// Field names are written as they are in Rust unless a different `KeyCase` is set
+    /// with `with_key_case`.
+    ///
+    /// ```
+    /// # use game_inventory::serialization::{InventorySerializer, JsonInventorySerializer};
+    /// let serializer = JsonInventorySerializer::<Vec<Option<(String, u16)>>>::new();
+    /// let inventory = vec![Some(("torch".to_owned(), 23)), None];
+    /// let json = serializer.serialize(&inventory);
+    /// assert_eq!(json, r#"[["torch",23],null]"#);
+    /// assert_eq!(serializer.deserialize(&json).unwrap(), inventory);
+    /// assert!(serializer.deserialize(&"[".to_owned()).is_err());
+    /// ```
+    #[derive(Debug, Clone, Copy, Default)]
+    pub struct JsonInventorySerializer<T> {
+        key_case: KeyCase,
+        phantom: PhantomData<T>,
+    }
+
+    /// How a `JsonInventorySerializer` writes the field names of structs.
+    ///
+    /// Keys that start with an uppercase letter, like enum variant names, are left as they are.
+    /// The keys of maps are renamed too, so avoid map keys with underscores or
+    /// uppercase letters when not using `KeyCase::Snake`.
+    #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+    pub enum KeyCase {
+        /// `max_quantity`, the same as the Rust field names.
+        #[default]
+        Snake,
+        /// `maxQuantity`, the usual style for JavaScript and web game backends.
+        Camel,
+    }
+
+    impl<T> JsonInventorySerializer<T> {
+        pub fn new() -> Self {
+            JsonInventorySerializer {
+                key_case: KeyCase::Snake,
+                phantom: PhantomData,
+            }
+        }
+
+        /// Sets how field names are written, and expected to be written when deserializing.
+        ///
+        /// ```
+        /// # use game_inventory::serialization::{InventorySerializer, JsonInventorySerializer, KeyCase};
+        /// # use std::collections::HashMap;
+        /// let serializer = JsonInventorySerializer::<HashMap<String, u16>>::new()
+        ///     .with_key_case(KeyCase::Camel);
+        /// let inventory = HashMap::from([("max_quantity".to_owned(), 23)]);
+        /// let json = serializer.serialize(&inventory);
+        /// assert_eq!(json, r#"{"maxQuantity":23}"#);
+        /// assert_eq!(serializer.deserialize(&json).unwrap(), inventory);
+        /// ```
+        pub fn with_key_case(mut self, key_case: KeyCase) -> Self {
+            self.key_case = key_case;
+            self
+        }
+    }
+
+    fn snake_to_camel(key: &str) -> String {
+        let mut camel = String::with_capacity(key.len());
+        let mut upper = false;
+        for c in key.chars() {
+            if c == '_' {
+                upper = true;
+            } else if upper {
+                camel.extend(c.to_uppercase());
+                upper = false;
+            } else {
+                camel.push(c);
+            }
+        }
+        camel
+    }
+
+    fn camel_to_snake(key: &str) -> String {
+        let mut snake = String::with_capacity(key.len() + 4);
+        for c in key.chars() {
+            if c.is_uppercase() {
+                snake.push('_');
+                snake.extend(c.to_lowercase());
+            } else {
+                snake.push(c);
+            }
+        }
+        snake
+    }
+
+    fn rename_keys(value: Value, rename: fn(&str) -> String) -> Value {
+        match value {
+            Value::Object(map) => Value::Object(
+                map.into_iter()
+                    .map(|(key, value)| {
+                        let key = match key.chars().next() {
+                            Some(c) if c.is_lowercase() => rename(&key),
+                            _ => key,
+                        };
+                        (key, rename_keys(value, rename))
+                    })
+                    .collect(),
+            ),
+            Value::Array(values) => Value::Array(
+                values
+                    .into_iter()
+                    .map(|value| rename_keys(value, rename))
+                    .collect(),
+            ),
+            value => value,
+        }
+    }
+
+    impl<T: Serialize + DeserializeOwned> InventorySerializer for JsonInventorySerializer<T> {
+        type Inventory = T;
+        type Output = String;
+
+        /// # Panics
+        ///
+        /// If the inventory cannot be represented as JSON, like a map with non string keys.
+        fn serialize(&self, inventory: &T) -> String {
+            match self.key_case {
+                KeyCase::Snake => serde_json::to_string(inventory),
+                KeyCase::Camel => serde_json::to_value(inventory)
+                    .and_then(|value| serde_json::to_string(&rename_keys(value, snake_to_camel))),
+            }
+            .expect("inventory could not be serialized to JSON")
+        }
+
+        fn deserialize(&self, data: &String) -> Result<T, DeserializeError> {
+            match self.key_case {
+                KeyCase::Snake => serde_json::from_str(data),
+                KeyCase::Camel => serde_json::from_str(data)
+                    .and_then(|value| serde_json::from_value(rename_keys(value, camel_to_snake))),
+            }
+            .map_err(|e| DeserializeError(e.to_string()))
+        }
+    }
+
+    /// Loads slots stored as JSON, then brings every quantity into its items `quantity_range`
+    /// with `clamp_inventory_quantities`, since saved data may have been edited by hand.
+    pub fn load_inventory_from_json<
+        Id: Eq,
+        I: Item<Id = Id>,
+        II: ItemInstance<I>,
+        S: Slot<I, II> + DeserializeOwned,
+    >(
+        json: &str,
+    ) -> Result<Vec<S>, DeserializeError> {
+        let mut inventory: Vec<S> =
+            serde_json::from_str(json).map_err(|e| DeserializeError(e.to_string()))?;
+        clamp_inventory_quantities(&mut inventory);
+        Ok(inventory)
+    }
+}
+
+#[cfg(feature = "bincode")]
+pub use binary::{load_inventory_binary, BinaryInventorySerializer};
+
+#[cfg(feature = "bincode")]
+mod binary {
+    use std::marker::PhantomData;
+
+    use serde::{de::DeserializeOwned, Serialize};
+
+    use super::InventorySerializer;
+    use crate::errors::DeserializeError;
+    use crate::inventory_management::clamp_inventory_quantities;
+    use crate::traits::{Item, ItemInstance, Slot};
+
+    /// Stores an inventory as bytes with `bincode`, which is smaller and faster than JSON.
+    ///
+    /// Use `load_inventory_binary` to load slots, which also cleans up their quantities.
+    ///
+    /// ```
+    /// # use game_inventory::serialization::{InventorySerializer, BinaryInventorySerializer};
+    /// let serializer = BinaryInventorySerializer::<Vec<Option<(String, u16)>>>::new();
+    /// let inventory = vec![Some(("torch".to_owned(), 23)), None];
+    /// let bytes = serializer.serialize(&inventory);
+    /// assert_eq!(serializer.deserialize(&bytes).unwrap(), inventory);
+    /// assert!(serializer.deserialize(&bytes[..4].to_vec()).is_err());
+    /// ```
+    #[derive(Debug, Clone, Copy, Default)]
+    pub struct BinaryInventorySerializer<T> {
+        phantom: PhantomData<T>,
+    }
+
+    impl<T> BinaryInventorySerializer<T> {
+        pub fn new() -> Self {
+            BinaryInventorySerializer {
+                phantom: PhantomData,
+            }
+        }
+    }
+
+    impl<T: Serialize + DeserializeOwned> InventorySerializer for BinaryInventorySerializer<T> {
+        type Inventory = T;
+        type Output = Vec<u8>;
+
+        /// # Panics
+        ///
+        /// If the inventory cannot be represented by `bincode`.
+        fn serialize(&self, inventory: &T) -> Vec<u8> {
+            bincode::serialize(inventory).expect("inventory could not be serialized with bincode")
+        }
+
+        fn deserialize(&self, data: &Vec<u8>) -> Result<T, DeserializeError> {
+            bincode::deserialize(data).map_err(|e| DeserializeError(e.to_string()))
+        }
+    }
+
+    /// Loads slots stored with `bincode`, then brings every quantity into its items
+    /// `quantity_range` with `clamp_inventory_quantities`.
+    pub fn load_inventory_binary<
+        Id: Eq,
+        I: Item<Id = Id>,
+        II: ItemInstance<I>,
+        S: Slot<I, II> + DeserializeOwned,
+    >(
+        data: &[u8],
+    ) -> Result<Vec<S>, DeserializeError> {
+        let mut inventory: Vec<S> =
+            bincode::deserialize(data).map_err(|e| DeserializeError(e.to_string()))?;
+        clamp_inventory_quantities(&mut inventory);
+        Ok(inventory)
+    }
+}
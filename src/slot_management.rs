@@ -2,7 +2,9 @@
 //!
 //! All methods, if they edit the item values, try to transfer
 //! the items from `items.0` to `items.1`.
-use crate::traits::{Item, ItemInstance};
+use crate::errors::{CursorError, InventoryError};
+use crate::inventory::InventoryIndex;
+use crate::traits::{Item, ItemInstance, Slot};
 use std::{error::Error, fmt::Display};
 
 #[derive(Debug)]
@@ -31,7 +33,7 @@ pub type Items<II> = (Option<II>, Option<II>);
 pub type ItemsRes<II> = Result<Items<II>, (Box<dyn std::error::Error>, Items<II>)>;
 
 /// Returns the inverse of the two inputs, specifically `(items.1, items.0)`.
-pub fn swap<'a, Id: Eq, I: Item<Id = Id>, II: ItemInstance<I>>(
+pub fn swap<Id: Eq, I: Item<Id = Id>, II: ItemInstance<I>>(
     items: (Option<II>, Option<II>),
 ) -> (Option<II>, Option<II>) {
     (items.1, items.0)
@@ -49,7 +51,7 @@ pub fn swap<'a, Id: Eq, I: Item<Id = Id>, II: ItemInstance<I>>(
 /// assert_eq!(items.0.as_ref().unwrap().item().id(), unwrapped.1.as_ref().unwrap().item().id());
 /// assert_eq!(items.0.as_ref().unwrap().quant(), unwrapped.1.unwrap().quant());
 /// ```
-pub fn swap_if_err<'a, Id: Eq, I: Item<Id = Id>, II: ItemInstance<I>>(
+pub fn swap_if_err<Id: Eq, I: Item<Id = Id>, II: ItemInstance<I>>(
     items: ItemsRes<II>,
 ) -> Items<II> {
     match items {
@@ -70,7 +72,7 @@ pub fn swap_if_err<'a, Id: Eq, I: Item<Id = Id>, II: ItemInstance<I>>(
 /// assert_eq!(items.0.unwrap().quant(), unwrapped.0.unwrap().quant());
 /// assert_eq!(items.1.is_none(), unwrapped.1.is_none());
 /// ```
-pub fn unwrap_items_res<'a, Id: Eq, I: Item<Id = Id>, II: ItemInstance<I>>(
+pub fn unwrap_items_res<Id: Eq, I: Item<Id = Id>, II: ItemInstance<I>>(
     items: ItemsRes<II>,
 ) -> Items<II> {
     match items {
@@ -86,10 +88,7 @@ pub fn unwrap_items_res<'a, Id: Eq, I: Item<Id = Id>, II: ItemInstance<I>>(
 /// # use game_inventory::helpers::combine_stack;
 /// # use std::sync::Arc;
 /// let items = (
-///     Some(DefaultItemInstance::<DefaultItem> {
-///         item: Arc::new(TORCH.clone()),
-///         quantity: 90,
-///     }),
+///     Some(DefaultItemInstance::<DefaultItem>::new(Arc::new(TORCH.clone()), 90).unwrap()),
 ///     TORCH_INST.clone(),
 /// );
 /// let res = combine_stack(items).ok().unwrap();
@@ -135,7 +134,7 @@ pub fn combine_stack<'a, Id: Eq, I: Item<Id = Id> + 'a, II: ItemInstance<I>>(
             items,
         ));
     }
-    if c.item().id() != o.item().id() {
+    if !c.item().stackable_with(o) {
         return Err((
             Box::new(SlotErr(
                 "Both items must be the same for this operation to work.".to_owned(),
@@ -157,10 +156,10 @@ pub fn combine_stack<'a, Id: Eq, I: Item<Id = Id> + 'a, II: ItemInstance<I>>(
         return Ok((None, Some(II::new(c.item(), combined))));
     }
     let left_over = combined - stack_size;
-    return Ok((
-        Some(II::new(c.item(), left_over)),
+    Ok((
+        (left_over > 0).then(|| II::new(c.item(), left_over)),
         Some(II::new(c.item(), stack_size)),
-    ));
+    ))
 }
 
 /// Splits a stack of items into two. Tries to split `items.0` and put the second half into `items.1`
@@ -171,14 +170,8 @@ pub fn combine_stack<'a, Id: Eq, I: Item<Id = Id> + 'a, II: ItemInstance<I>>(
 /// # use game_inventory::traits::{Item, ItemInstance};
 /// # use std::sync::Arc;
 /// let res = half_stack_split((
-/// Some(DefaultItemInstance {
-///     item: Arc::new(TORCH.clone()),
-///     quantity: 11,
-/// }),
-/// Some(DefaultItemInstance {
-///     item: Arc::new(TORCH.clone()),
-///     quantity: 3,
-/// }),
+/// Some(DefaultItemInstance::new(Arc::new(TORCH.clone()), 11).unwrap()),
+/// Some(DefaultItemInstance::new(Arc::new(TORCH.clone()), 3).unwrap()),
 /// )).ok().unwrap();
 /// assert!(res.0.as_ref().unwrap().item().id() == TORCH.id());
 /// assert!(res.0.unwrap().quant() == 5);
@@ -221,7 +214,7 @@ pub fn half_stack_split<'a, Id: Eq, I: Item<Id = Id> + 'a, II: ItemInstance<I>>(
         ));
     }
     if match &items.1 {
-        Some(o) => c.item().id() != o.item().id(),
+        Some(o) => !c.item().stackable_with(o),
         None => false,
     } {
         return Err((
@@ -244,13 +237,13 @@ pub fn half_stack_split<'a, Id: Eq, I: Item<Id = Id> + 'a, II: ItemInstance<I>>(
         None => 0,
     };
     let half_stack = c.quant() / 2;
-    return Ok((
+    Ok((
         Some(II::new(c.item(), half_stack)),
         Some(II::new(
             c.item(),
             other_quant + half_stack + (c.quant() % 2),
         )),
-    ));
+    ))
 }
 
 /// Removes a single item from a stack. Tries to take a single item from `items.0` and put it into `items.1`.
@@ -261,10 +254,7 @@ pub fn half_stack_split<'a, Id: Eq, I: Item<Id = Id> + 'a, II: ItemInstance<I>>(
 /// # use game_inventory::traits::{ItemInstance, Item};
 /// # use std::sync::Arc;
 /// let res = remove_from_stack((
-///     Some(DefaultItemInstance::<DefaultItem> {
-///         item: Arc::new(TORCH.clone()),
-///         quantity: 3,
-///     }),
+///     Some(DefaultItemInstance::<DefaultItem>::new(Arc::new(TORCH.clone()), 3).unwrap()),
 ///     TORCH_INST.clone(),
 /// )).ok().unwrap();
 /// assert!(res.0.as_ref().unwrap().item().id() == TORCH.id());
@@ -279,14 +269,8 @@ pub fn half_stack_split<'a, Id: Eq, I: Item<Id = Id> + 'a, II: ItemInstance<I>>(
 /// # use game_inventory::traits::{ItemInstance, Item};
 /// # use std::sync::Arc;
 /// let res = remove_from_stack((
-///     Some(DefaultItemInstance::<DefaultItem> {
-///         item: Arc::new(TORCH.clone()),
-///         quantity: 1,
-///     }),
-///     Some(DefaultItemInstance::<DefaultItem> {
-///         item: Arc::new(TORCH.clone()),
-///         quantity: 20,
-///     }),
+///     Some(DefaultItemInstance::<DefaultItem>::new(Arc::new(TORCH.clone()), 1).unwrap()),
+///     Some(DefaultItemInstance::<DefaultItem>::new(Arc::new(TORCH.clone()), 20).unwrap()),
 /// )).ok().unwrap();
 /// assert!(res.1.clone().unwrap().item().id() == TORCH.id());
 /// assert!(res.1.unwrap().quant() == 21);
@@ -299,10 +283,7 @@ pub fn half_stack_split<'a, Id: Eq, I: Item<Id = Id> + 'a, II: ItemInstance<I>>(
 /// # use game_inventory::traits::{ItemInstance, Item};
 /// # use std::sync::Arc;
 /// let res = remove_from_stack((
-///     Some(DefaultItemInstance {
-///         item: Arc::new(TORCH.clone()),
-///         quantity: 3,
-///     }),
+///     Some(DefaultItemInstance::new(Arc::new(TORCH.clone()), 3).unwrap()),
 ///     None,
 /// )).ok().unwrap();
 /// assert!(res.0.as_ref().unwrap().item().id() == TORCH.id());
@@ -357,7 +338,7 @@ pub fn remove_from_stack<'a, Id: Eq, I: Item<Id = Id> + 'a, II: ItemInstance<I>>
             ));
         }
     };
-    if o.item().id() != c.item().id() {
+    if !c.item().stackable_with(o) {
         return Err((
             Box::new(SlotErr(
                 "Both items must be the same for this operation to work.".to_owned(),
@@ -376,8 +357,440 @@ pub fn remove_from_stack<'a, Id: Eq, I: Item<Id = Id> + 'a, II: ItemInstance<I>>
     if c.quant() < 2 {
         return Ok((None, Some(II::new(o.item(), o.quant() + 1))));
     }
-    return Ok((
+    Ok((
         Some(II::new(c.item(), c.quant() - 1)),
         Some(II::new(o.item(), o.quant() + 1)),
-    ));
+    ))
+}
+
+/// Duplicates a slot and the item it holds.
+///
+/// This is the same as `source.clone()`, but makes it clear at the call site
+/// that the items are being copied and not transferred, see `move_slot`.
+///
+/// ```
+/// # use game_inventory::samples::{DefaultSlot, TORCH_INST};
+/// # use game_inventory::traits::{Slot, ItemInstance};
+/// # use game_inventory::helpers::copy_slot;
+/// let source = DefaultSlot::new(TORCH_INST.clone());
+/// let copy = copy_slot(&source);
+/// assert_eq!(source.quantity(), 23);
+/// assert_eq!(copy.quantity(), 23);
+/// ```
+pub fn copy_slot<Id: Eq, I: Item<Id = Id>, II: ItemInstance<I>, S: Slot<I, II> + Clone>(
+    source: &S,
+) -> S {
+    source.clone()
+}
+
+/// Moves the item in `source` into `dest`, leaving `source` empty.
+///
+/// ```
+/// # use game_inventory::samples::{DefaultSlot, TORCH_INST};
+/// # use game_inventory::traits::Slot;
+/// # use game_inventory::helpers::move_slot;
+/// let mut source = DefaultSlot::new(TORCH_INST.clone());
+/// let mut dest = DefaultSlot::new(None);
+/// move_slot(&mut source, &mut dest).unwrap();
+/// assert!(source.item_instance().is_none());
+/// assert_eq!(dest.quantity(), 23);
+/// ```
+/// You will not be able to move the item if:
+/// ```
+/// # use game_inventory::samples::{DefaultSlot, DefaultItem, TORCH_INST, JUNK_INST};
+/// # use game_inventory::traits::{Slot, Item};
+/// # use game_inventory::helpers::move_slot;
+/// # use game_inventory::errors::InventoryError;
+/// // source is empty.
+/// let mut source = DefaultSlot::new(None);
+/// let mut dest = DefaultSlot::new(JUNK_INST.clone());
+/// assert_eq!(move_slot(&mut source, &mut dest), Err(InventoryError::SlotEmpty));
+/// // dest already holds an item.
+/// let mut source = DefaultSlot::new(TORCH_INST.clone());
+/// let mut dest = DefaultSlot::new(JUNK_INST.clone());
+/// assert_eq!(move_slot(&mut source, &mut dest), Err(InventoryError::SlotOccupied));
+/// assert_eq!(source.quantity(), 23);
+/// // dest does not accept the item.
+/// let mut dest = DefaultSlot::new(None);
+/// dest.item_filter = Some(|item: &DefaultItem| !item.stackable());
+/// assert_eq!(move_slot(&mut source, &mut dest), Err(InventoryError::ItemNotAccepted));
+/// ```
+pub fn move_slot<Id: Eq, I: Item<Id = Id>, II: ItemInstance<I>, S: Slot<I, II>>(
+    source: &mut S,
+    dest: &mut S,
+) -> Result<(), InventoryError> {
+    let item_instance = match source.item_instance() {
+        None => return Err(InventoryError::SlotEmpty),
+        Some(i) => i,
+    };
+    if dest.is_occupied() {
+        return Err(InventoryError::SlotOccupied);
+    }
+    if !dest.accepts(&item_instance.item()) {
+        return Err(InventoryError::ItemNotAccepted);
+    }
+    source.set_item_instance(&None);
+    dest.set_item_instance(&Some(item_instance));
+    Ok(())
+}
+
+/// Moves the item at `from` into the slot at `to` within the same inventory.
+///
+/// Works the same as `move_slot`. Moving a slot onto itself does nothing.
+///
+/// ```
+/// # use game_inventory::samples::{DefaultSlot, TORCH_INST};
+/// # use game_inventory::traits::Slot;
+/// # use game_inventory::helpers::move_slot_in_inventory;
+/// # use game_inventory::errors::InventoryError;
+/// let mut inventory = vec![DefaultSlot::new(TORCH_INST.clone()), DefaultSlot::new(None)];
+/// move_slot_in_inventory(&mut inventory, 0, 1).unwrap();
+/// assert!(inventory[0].item_instance().is_none());
+/// assert_eq!(inventory[1].quantity(), 23);
+/// assert_eq!(
+///     move_slot_in_inventory(&mut inventory, 1, 2),
+///     Err(InventoryError::IndexOutOfBounds(2))
+/// );
+/// ```
+pub fn move_slot_in_inventory<Id: Eq, I: Item<Id = Id>, II: ItemInstance<I>, S: Slot<I, II>>(
+    inventory: &mut [S],
+    from: impl Into<InventoryIndex>,
+    to: impl Into<InventoryIndex>,
+) -> Result<(), InventoryError> {
+    let (from, to) = (from.into().get(), to.into().get());
+    if from == to {
+        return match inventory.get(from) {
+            None => Err(InventoryError::IndexOutOfBounds(from)),
+            Some(_) => Ok(()),
+        };
+    }
+    let (source, dest) = slot_pair_mut(inventory, from, to)?;
+    move_slot(source, dest)
+}
+
+/// Checks if some of the items in `from` could be merged into `into`, without changing either slot.
+///
+/// Both slots have to hold the same item, and `into` has to have room left below its `capacity`.
+/// ```
+/// # use game_inventory::samples::{DefaultSlot, TORCH_INST, TORCH_FULL_STACK_INST, JUNK_INST, SWORD_INST};
+/// # use game_inventory::traits::Slot;
+/// # use game_inventory::helpers::can_merge_slots;
+/// let torch = DefaultSlot::new(TORCH_INST.clone());
+/// assert!(can_merge_slots(&torch, &torch));
+/// assert!(!can_merge_slots(&torch, &DefaultSlot::new(TORCH_FULL_STACK_INST.clone())));
+/// assert!(!can_merge_slots(&torch, &DefaultSlot::new(JUNK_INST.clone())));
+/// assert!(!can_merge_slots(&torch, &DefaultSlot::new(None)));
+/// let sword = DefaultSlot::new(SWORD_INST.clone());
+/// assert!(!can_merge_slots(&sword, &sword));
+/// ```
+pub fn can_merge_slots<Id: Eq, I: Item<Id = Id>, II: ItemInstance<I>, S: Slot<I, II>>(
+    from: &S,
+    into: &S,
+) -> bool {
+    match (from.item_instance(), into.item_instance()) {
+        (Some(f), Some(i)) => i.item().stackable_with(&f) && i.quant() < into.capacity(),
+        _ => false,
+    }
+}
+
+/// The most items that could be merged from `from` into `into`, see `can_merge_slots`.
+///
+/// Returns `0` if the slots cannot be merged.
+/// ```
+/// # use game_inventory::samples::{DefaultSlot, DefaultItemInstance, TORCH, TORCH_INST, JUNK_INST};
+/// # use game_inventory::traits::{Slot, ItemInstance};
+/// # use game_inventory::helpers::merge_capacity;
+/// # use std::sync::Arc;
+/// let torch = DefaultSlot::new(TORCH_INST.clone());
+/// let many_torches = DefaultSlot::new(Some(DefaultItemInstance::new(Arc::new(TORCH.clone()), 90).unwrap()));
+/// assert_eq!(merge_capacity(&torch, &many_torches), 10);
+/// assert_eq!(merge_capacity(&many_torches, &torch), 77);
+/// assert_eq!(merge_capacity(&torch, &torch), 23);
+/// assert_eq!(merge_capacity(&torch, &DefaultSlot::new(JUNK_INST.clone())), 0);
+/// ```
+pub fn merge_capacity<Id: Eq, I: Item<Id = Id>, II: ItemInstance<I>, S: Slot<I, II>>(
+    from: &S,
+    into: &S,
+) -> u16 {
+    if !can_merge_slots(from, into) {
+        return 0;
+    }
+    let space = into.capacity().saturating_sub(into.quantity());
+    from.quantity().min(space)
+}
+
+/// Moves the stack at `from` onto the slot at `to` within the same inventory.
+///
+/// If `to` is empty the whole stack is moved. If it holds the same item the stacks are
+/// merged, leaving whatever does not fit in `from`. Unlike `move_slot_in_inventory`,
+/// which only moves into empty slots, this is for dropping a stack onto any slot.
+/// ```
+/// # use game_inventory::samples::{DefaultSlot, DefaultItemInstance, TORCH, TORCH_INST, JUNK_INST};
+/// # use game_inventory::traits::{Slot, ItemInstance};
+/// # use game_inventory::helpers::move_stack;
+/// # use game_inventory::errors::InventoryError;
+/// # use std::sync::Arc;
+/// let mut inventory = vec![
+///     DefaultSlot::new(TORCH_INST.clone()),
+///     DefaultSlot::new(None),
+///     DefaultSlot::new(Some(DefaultItemInstance::new(Arc::new(TORCH.clone()), 90).unwrap())),
+///     DefaultSlot::new(JUNK_INST.clone()),
+/// ];
+/// move_stack(&mut inventory, 0, 1).unwrap();
+/// assert_eq!(inventory[1].quantity(), 23);
+/// move_stack(&mut inventory, 1, 2).unwrap();
+/// assert_eq!(inventory[1].quantity(), 13);
+/// assert_eq!(inventory[2].quantity(), 100);
+/// assert_eq!(move_stack(&mut inventory, 1, 3), Err(InventoryError::SlotOccupied));
+/// assert_eq!(move_stack(&mut inventory, 0, 3), Err(InventoryError::SlotEmpty));
+/// ```
+pub fn move_stack<Id: Eq, I: Item<Id = Id>, II: ItemInstance<I>, S: Slot<I, II>>(
+    inventory: &mut [S],
+    from: impl Into<InventoryIndex>,
+    to: impl Into<InventoryIndex>,
+) -> Result<(), InventoryError> {
+    let (from, to) = (from.into().get(), to.into().get());
+    if from == to {
+        return match inventory.get(from) {
+            None => Err(InventoryError::IndexOutOfBounds(from)),
+            Some(_) => Ok(()),
+        };
+    }
+    let (source, dest) = slot_pair_mut(inventory, from, to)?;
+    if dest.is_empty() {
+        return move_slot(source, dest);
+    }
+    if let Some(source_ii) = source.item_instance() {
+        if !dest.accepts(&source_ii.item()) {
+            return Err(InventoryError::ItemNotAccepted);
+        }
+    }
+    let moved = merge_capacity(source, dest);
+    let (source_ii, dest_ii) = match (source.item_instance(), dest.item_instance()) {
+        (Some(s), Some(d)) if moved > 0 => (s, d),
+        (None, _) => return Err(InventoryError::SlotEmpty),
+        _ => return Err(InventoryError::SlotOccupied),
+    };
+    dest.set_item_instance(&Some(II::new(dest_ii.item(), dest_ii.quant() + moved)));
+    let left = source_ii.quant() - moved;
+    source.set_item_instance(&(left > 0).then(|| II::new(source_ii.item(), left)));
+    Ok(())
+}
+
+/// Gets mutable references to two different slots in an inventory.
+fn slot_pair_mut<S>(
+    inventory: &mut [S],
+    a: usize,
+    b: usize,
+) -> Result<(&mut S, &mut S), InventoryError> {
+    for index in [a, b] {
+        if index >= inventory.len() {
+            return Err(InventoryError::IndexOutOfBounds(index));
+        }
+    }
+    if a < b {
+        let (left, right) = inventory.split_at_mut(b);
+        return Ok((&mut left[a], &mut right[0]));
+    }
+    let (left, right) = inventory.split_at_mut(a);
+    Ok((&mut right[0], &mut left[b]))
+}
+
+/// Something that happened to a single slot, see `Slot::emit_event`.
+///
+/// These are for slot level UI behavior that the rest of the inventory does not need to know about.
+#[derive(Debug, Clone)]
+pub enum SlotEvent<II> {
+    /// The cursor started hovering over the slot.
+    Hovered,
+    /// The cursor stopped hovering over the slot.
+    Unhovered,
+    /// The slot was selected.
+    Selected,
+    /// The slot was deselected.
+    Deselected,
+    /// The item in the slot started being dragged.
+    DragStart(II),
+    /// The item being dragged from the slot was let go.
+    DragEnd,
+    /// The slot still holds the same item, but the quantity changed.
+    QuantityChanged { old: u16, new: u16 },
+    /// The slot went from holding one item to another.
+    ItemSwapped { old: II, new: II },
+}
+
+/// What happened to the item held by a `CursorSlot` when it was dropped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DropResult {
+    /// The slot was empty, and now holds the item.
+    Placed,
+    /// The slot held a different item, which the cursor is now holding.
+    Swapped,
+    /// The item was merged into the stack in the slot.
+    Merged,
+    /// Part of the item was put into the slot, the cursor holds the rest.
+    MergedWithRemainder,
+}
+
+/// The item being held by the mouse cursor in a drag and drop UI.
+///
+/// ```
+/// # use game_inventory::samples::{DefaultSlot, DefaultItemInstance, TORCH, TORCH_INST, JUNK_INST};
+/// # use game_inventory::traits::{Slot, ItemInstance};
+/// # use game_inventory::helpers::{CursorSlot, DropResult};
+/// # use std::sync::Arc;
+/// let mut inventory = vec![
+///     DefaultSlot::new(TORCH_INST.clone()),
+///     DefaultSlot::new(None),
+///     DefaultSlot::new(JUNK_INST.clone()),
+///     DefaultSlot::new(Some(DefaultItemInstance::new(Arc::new(TORCH.clone()), 90).unwrap())),
+/// ];
+/// let mut cursor = CursorSlot::new();
+/// cursor.pick_up_from(&mut inventory[0]).unwrap();
+/// assert_eq!(cursor.drop_onto(&mut inventory[1]), Ok(DropResult::Placed));
+/// assert!(cursor.is_empty());
+///
+/// cursor.pick_up_from(&mut inventory[1]).unwrap();
+/// assert_eq!(cursor.drop_onto(&mut inventory[2]), Ok(DropResult::Swapped));
+/// assert_eq!(inventory[2].quantity(), 23);
+/// assert_eq!(cursor.drop_onto(&mut inventory[1]), Ok(DropResult::Placed));
+///
+/// cursor.pick_up_from(&mut inventory[2]).unwrap();
+/// assert_eq!(cursor.drop_onto(&mut inventory[3]), Ok(DropResult::MergedWithRemainder));
+/// assert_eq!(inventory[3].quantity(), 100);
+/// assert_eq!(cursor.0.as_ref().unwrap().quant(), 13);
+/// ```
+/// You will not be able to pick up or drop an item if:
+/// ```
+/// # use game_inventory::samples::{DefaultSlot, TORCH_INST};
+/// # use game_inventory::traits::Slot;
+/// # use game_inventory::helpers::CursorSlot;
+/// # use game_inventory::errors::CursorError;
+/// let mut inventory = vec![DefaultSlot::new(TORCH_INST.clone()), DefaultSlot::new(TORCH_INST.clone()), DefaultSlot::new(None)];
+/// let mut cursor = CursorSlot::new();
+/// // The cursor is not holding an item.
+/// assert_eq!(cursor.drop_onto(&mut inventory[2]), Err(CursorError::NothingHeld));
+/// // The slot is empty.
+/// assert_eq!(cursor.pick_up_from(&mut inventory[2]), Err(CursorError::NothingToPickUp));
+/// // The cursor is already holding an item.
+/// cursor.pick_up_from(&mut inventory[0]).unwrap();
+/// assert_eq!(cursor.pick_up_from(&mut inventory[1]), Err(CursorError::AlreadyHolding));
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CursorSlot<II>(pub Option<II>);
+
+impl<II> CursorSlot<II> {
+    /// Creates a cursor that is not holding anything.
+    pub fn new() -> Self {
+        CursorSlot(None)
+    }
+
+    /// Whether the cursor is not holding an item.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_none()
+    }
+
+    /// Takes the item out of `source` and holds it.
+    pub fn pick_up_from<Id: Eq, I: Item<Id = Id>, S: Slot<I, II>>(
+        &mut self,
+        source: &mut S,
+    ) -> Result<(), CursorError>
+    where
+        II: ItemInstance<I>,
+    {
+        if self.0.is_some() {
+            return Err(CursorError::AlreadyHolding);
+        }
+        let item_instance = match source.item_instance() {
+            None => return Err(CursorError::NothingToPickUp),
+            Some(i) => i,
+        };
+        source.set_item_instance(&None);
+        self.0 = Some(item_instance);
+        Ok(())
+    }
+
+    /// Drops the held item onto `dest`.
+    ///
+    /// If `dest` holds the same item they are merged, with anything that does
+    /// not fit staying on the cursor. If it holds a different item, or a full stack,
+    /// the two are swapped. Only as many items as fit in the `capacity` of `dest` are put in it.
+    ///
+    /// Returns `CursorError::ItemNotAccepted` without changing anything if `dest` does not
+    /// accept the held item, or if swapping would put more in `dest` than it can hold.
+    /// ```
+    /// # use game_inventory::samples::{DefaultSlot, DefaultItemInstance, TORCH, TORCH_INST, SWORD_INST};
+    /// # use game_inventory::traits::{Slot, Item, ItemInstance};
+    /// # use game_inventory::helpers::{CursorSlot, DropResult};
+    /// # use game_inventory::errors::CursorError;
+    /// # use std::sync::Arc;
+    /// let mut slot = DefaultSlot::new(TORCH_INST.clone());
+    /// slot.capacity_override = Some(30);
+    /// let mut cursor = CursorSlot(TORCH_INST.clone());
+    /// assert_eq!(cursor.drop_onto(&mut slot), Ok(DropResult::MergedWithRemainder));
+    /// assert_eq!(slot.quantity(), 30);
+    /// assert_eq!(cursor.0.as_ref().unwrap().quant(), 16);
+    ///
+    /// slot.item_filter = Some(|item| item.stackable());
+    /// let mut cursor = CursorSlot(SWORD_INST.clone());
+    /// assert_eq!(cursor.drop_onto(&mut slot), Err(CursorError::ItemNotAccepted));
+    /// assert!(!cursor.is_empty());
+    /// ```
+    pub fn drop_onto<Id: Eq, I: Item<Id = Id>, S: Slot<I, II>>(
+        &mut self,
+        dest: &mut S,
+    ) -> Result<DropResult, CursorError>
+    where
+        II: ItemInstance<I>,
+    {
+        let held = match self.0.take() {
+            None => return Err(CursorError::NothingHeld),
+            Some(h) => h,
+        };
+        if !dest.accepts(&held.item()) {
+            self.0 = Some(held);
+            return Err(CursorError::ItemNotAccepted);
+        }
+        let capacity = dest.capacity_for(&held.item());
+        let current = match dest.item_instance() {
+            None if !held.item().stackable() || held.quant() <= capacity => {
+                dest.set_item_instance(&Some(held));
+                return Ok(DropResult::Placed);
+            }
+            None if capacity == 0 => {
+                self.0 = Some(held);
+                return Err(CursorError::ItemNotAccepted);
+            }
+            None => {
+                dest.set_item_instance(&Some(II::new(held.item(), capacity)));
+                self.0 = Some(II::new(held.item(), held.quant() - capacity));
+                return Ok(DropResult::MergedWithRemainder);
+            }
+            Some(current) => current,
+        };
+        if current.item().stackable_with(&held) && current.quant() < dest.capacity() {
+            return match dest.try_add_quantity(held.quant()) {
+                0 => Ok(DropResult::Merged),
+                left => {
+                    self.0 = Some(II::new(held.item(), left));
+                    Ok(DropResult::MergedWithRemainder)
+                }
+            };
+        }
+        if held.item().stackable() && held.quant() > capacity {
+            self.0 = Some(held);
+            return Err(CursorError::ItemNotAccepted);
+        }
+        dest.set_item_instance(&Some(held));
+        self.0 = Some(current);
+        Ok(DropResult::Swapped)
+    }
+}
+
+impl<II> Default for CursorSlot<II> {
+    fn default() -> Self {
+        Self::new()
+    }
 }
@@ -1,8 +1,13 @@
 //! All traits that are needed to interface with the inventory system.
 
+use std::fmt::Display;
+use std::ops::RangeInclusive;
 use std::sync::Arc;
 
-use crate::slot_management::swap;
+#[cfg(feature = "tooltip")]
+use crate::display::TooltipLine;
+use crate::inventory_management::ScaleResult;
+use crate::slot_management::{swap, SlotEvent};
 /// Trait for defining what static item data is necessary for the inventory system.
 ///
 /// Static item data are things like the items name, the items base damage. Data
@@ -18,6 +23,99 @@ pub trait Item {
     fn max_quant(&self) -> u16;
     /// The Unique ID of the item
     fn id(&self) -> Self::Id;
+    /// Whether an instance of this item can be put into the same stack as `other`.
+    ///
+    /// By default items stack with instances of the same item, if they are stackable at all.
+    /// Override this for items that only stack when their attributes match,
+    /// like two fire-enchanted swords that should not stack with a frost-enchanted one.
+    /// The helpers that merge stacks check this instead of comparing ids.
+    /// ```
+    /// # use game_inventory::samples::{TORCH, SWORD, TORCH_INST, JUNK_INST, SWORD_INST};
+    /// # use game_inventory::traits::Item;
+    /// assert!(TORCH.stackable_with(TORCH_INST.as_ref().unwrap()));
+    /// assert!(!TORCH.stackable_with(JUNK_INST.as_ref().unwrap()));
+    /// assert!(!SWORD.stackable_with(SWORD_INST.as_ref().unwrap()));
+    /// ```
+    fn stackable_with<II: ItemInstance<Self>>(&self, other: &II) -> bool
+    where
+        Self: Sized,
+    {
+        self.stackable() && self.id() == other.item().id()
+    }
+    /// The quantities an instance of this item can be created with.
+    ///
+    /// By default this is `1..=max_quant()` for stackable items and `0..=0` for unstackable ones.
+    /// A stackable item with a `max_quant` of `0` still allows a quantity of `1`, so the range is never empty.
+    /// Override it for items that have a minimum stack size.
+    /// ```
+    /// # use game_inventory::samples::{TORCH, SWORD};
+    /// # use game_inventory::traits::Item;
+    /// assert_eq!(TORCH.quantity_range(), 1..=100);
+    /// assert_eq!(SWORD.quantity_range(), 0..=0);
+    /// ```
+    fn quantity_range(&self) -> RangeInclusive<u16> {
+        match self.stackable() {
+            true => 1..=self.max_quant().max(1),
+            false => 0..=0,
+        }
+    }
+    /// A description of the item for showing in tooltips.
+    fn description(&self) -> Option<&str> {
+        None
+    }
+    /// Lore text about the item, usually shown in italics below the description.
+    fn flavor_text(&self) -> Option<&str> {
+        None
+    }
+    /// The id of the items sprite in a texture atlas.
+    fn icon_id(&self) -> Option<u32> {
+        None
+    }
+    /// The path to the items icon, for games that load each sprite from its own file.
+    fn icon_path(&self) -> Option<&str> {
+        None
+    }
+    /// Freeform tags for the item, like `"fire"` or `"consumable"`.
+    ///
+    /// Unlike `ItemCategory`, an item can have any number of tags.
+    fn tags(&self) -> &[&str] {
+        &[]
+    }
+    /// The lines of the items tooltip, each with a style so they can be drawn differently.
+    ///
+    /// Empty by default.
+    #[cfg(feature = "tooltip")]
+    fn tooltip_lines(&self) -> Vec<TooltipLine> {
+        Vec::new()
+    }
+}
+/// Trait for items that are grouped into categories, like weapons or consumables.
+///
+/// Used for things like showing a count of items on each tab of the inventory UI.
+pub trait ItemCategory {
+    /// The name of the category this item belongs to.
+    fn category(&self) -> &str;
+}
+/// Trait for crafting recipes, which turn a list of ingredients into an item.
+///
+/// See `can_craft` and `craft_item`.
+pub trait Craftable {
+    /// The type of item that is both used and made by this recipe.
+    type Output: Item;
+    /// The id and quantity of each item that is used up by the recipe.
+    fn ingredients(&self) -> Vec<(<Self::Output as Item>::Id, u16)>;
+    /// The item made by the recipe and how many of it are made.
+    fn output(&self) -> (Self::Output, u16);
+}
+/// Trait for items that have a weight, for encumbrance.
+pub trait ItemWeight {
+    /// The weight of a single one of this item.
+    fn weight(&self) -> f32;
+}
+/// Trait for items that are worth something, for shops and loot.
+pub trait ItemValue {
+    /// The value of a single one of this item.
+    fn value(&self) -> u64;
 }
 /// Trait for storing item instance data.
 ///
@@ -30,6 +128,112 @@ pub trait ItemInstance<I: Item> {
     fn item(&self) -> Arc<I>;
     /// Creates a new item instance.
     fn new(item: Arc<I>, quantity: u16) -> Self;
+    /// Whether two item instances are identical.
+    ///
+    /// By default this compares the item id and quantity. Override it to also compare
+    /// any other instance data, like durability or enchantments.
+    /// ```
+    /// # use game_inventory::samples::{DefaultItemInstance, TORCH, TORCH_INST, JUNK_INST};
+    /// # use game_inventory::traits::ItemInstance;
+    /// # use std::sync::Arc;
+    /// let torch = TORCH_INST.clone().unwrap();
+    /// assert!(torch.is_same_as(&DefaultItemInstance::new(Arc::new(TORCH.clone()), 23).unwrap()));
+    /// assert!(!torch.is_same_as(&DefaultItemInstance::new(Arc::new(TORCH.clone()), 24).unwrap()));
+    /// assert!(!torch.is_same_as(&JUNK_INST.clone().unwrap()));
+    /// assert_eq!(torch, DefaultItemInstance::new(Arc::new(TORCH.clone()), 23).unwrap());
+    /// ```
+    fn is_same_as(&self, other: &Self) -> bool
+    where
+        Self: Sized,
+    {
+        self.item().id() == other.item().id() && self.quant() == other.quant()
+    }
+    /// Multiplies the quantity by `factor`, rounding down.
+    ///
+    /// The quantity never goes below `1` or above `max_quant()`, only a `factor` of `0.0`
+    /// or less clears the instance. Does nothing to unstackable items, empty instances
+    /// and a `factor` of NaN.
+    /// ```
+    /// # use game_inventory::samples::{DefaultItemInstance, TORCH, SWORD_INST};
+    /// # use game_inventory::traits::ItemInstance;
+    /// # use game_inventory::helpers::ScaleResult;
+    /// # use std::sync::Arc;
+    /// let mut arrows = DefaultItemInstance::new(Arc::new(TORCH.clone()), 50).unwrap();
+    /// assert_eq!(arrows.scale_quantity(0.8), ScaleResult::Reduced { by: 10 });
+    /// assert_eq!(arrows.scale_quantity(0.01), ScaleResult::Reduced { by: 39 });
+    /// assert_eq!(arrows.quant(), 1);
+    /// assert_eq!(arrows.scale_quantity(500.0), ScaleResult::Increased { by: 99 });
+    /// assert_eq!(arrows.scale_quantity(1.0), ScaleResult::Unchanged);
+    /// assert_eq!(arrows.scale_quantity(f32::NAN), ScaleResult::Unchanged);
+    /// assert_eq!(arrows.quant(), 100);
+    /// assert_eq!(arrows.scale_quantity(0.0), ScaleResult::Cleared);
+    /// assert_eq!(arrows.quant(), 0);
+    /// assert_eq!(SWORD_INST.clone().unwrap().scale_quantity(0.5), ScaleResult::Unchanged);
+    /// ```
+    fn scale_quantity(&mut self, factor: f32) -> ScaleResult
+    where
+        Self: Sized,
+    {
+        let item = self.item();
+        let quant = self.quant();
+        if !item.stackable() || quant == 0 || factor.is_nan() {
+            return ScaleResult::Unchanged;
+        }
+        if factor <= 0.0 {
+            *self = Self::new(item, 0);
+            return ScaleResult::Cleared;
+        }
+        let scaled = (quant as f32 * factor).floor();
+        let scaled = scaled.clamp(1.0, item.max_quant().max(1) as f32) as u16;
+        *self = Self::new(item, scaled);
+        match scaled.cmp(&quant) {
+            std::cmp::Ordering::Less => ScaleResult::Reduced { by: quant - scaled },
+            std::cmp::Ordering::Equal => ScaleResult::Unchanged,
+            std::cmp::Ordering::Greater => ScaleResult::Increased { by: scaled - quant },
+        }
+    }
+    /// Brings the quantity into the valid range for the item.
+    ///
+    /// Stackable items end up with between `1` and `max_quant()` items,
+    /// unstackable items end up with a quantity of `0`. Useful for cleaning up
+    /// item instances loaded from a save file or created from random data.
+    /// Never panics, even if an overridden `quantity_range` is empty, the end of the range wins then.
+    ///
+    /// ```
+    /// # use game_inventory::samples::{DefaultItemInstance, TORCH, SWORD};
+    /// # use game_inventory::traits::ItemInstance;
+    /// # use std::sync::Arc;
+    /// let too_many = DefaultItemInstance::new_unchecked(Arc::new(TORCH.clone()), 500);
+    /// assert_eq!(too_many.clamp_quantity().quant(), 100);
+    /// let none = DefaultItemInstance::new_unchecked(Arc::new(TORCH.clone()), 0);
+    /// assert_eq!(none.clamp_quantity().quant(), 1);
+    /// let sword = DefaultItemInstance::new_unchecked(Arc::new(SWORD.clone()), 3);
+    /// assert_eq!(sword.clamp_quantity().quant(), 0);
+    /// ```
+    fn clamp_quantity(self) -> Self
+    where
+        Self: Sized,
+    {
+        let item = self.item();
+        let range = item.quantity_range();
+        let quantity = self.quant().max(*range.start()).min(*range.end());
+        Self::new(item, quantity)
+    }
+    /// Puts this item instance into a new slot.
+    ///
+    /// ```
+    /// # use game_inventory::samples::{DefaultSlot, DefaultItem, DefaultItemInstance, TORCH_INST};
+    /// # use game_inventory::traits::{Slot, ItemInstance};
+    /// let slot: DefaultSlot<DefaultItem, DefaultItemInstance<DefaultItem>> =
+    ///     TORCH_INST.clone().unwrap().into_slot();
+    /// assert_eq!(slot.quantity(), 23);
+    /// ```
+    fn into_slot<S: Slot<I, Self>>(self) -> S
+    where
+        Self: Sized,
+    {
+        S::new(Some(self))
+    }
 }
 /// Trait for defining an item slot.
 ///
@@ -42,6 +246,186 @@ pub trait Slot<I: Item, II: ItemInstance<I>> {
     fn item_instance(&self) -> Option<II>;
     /// Set the item instance stored by this slot.
     fn set_item_instance(&mut self, item_instance: &Option<II>);
+    /// Checks if the slot has no item instance in it.
+    ///
+    /// ```
+    /// # use game_inventory::samples::{DefaultSlot, TORCH_INST};
+    /// # use game_inventory::traits::Slot;
+    /// let mut slot = DefaultSlot::new(TORCH_INST.clone());
+    /// assert!(slot.is_occupied());
+    /// slot.set_item_instance(&None);
+    /// assert!(slot.is_empty());
+    /// ```
+    fn is_empty(&self) -> bool {
+        self.item_instance().is_none()
+    }
+    /// Checks if the slot has an item instance in it, the opposite of `is_empty`.
+    fn is_occupied(&self) -> bool {
+        !self.is_empty()
+    }
+    /// The quantity of the item instance stored by this slot, or `0` if the slot is empty.
+    ///
+    /// ```
+    /// # use game_inventory::samples::{DefaultSlot, TORCH_INST};
+    /// # use game_inventory::traits::{Slot, ItemInstance};
+    /// let inventory = vec![DefaultSlot::new(TORCH_INST.clone()), DefaultSlot::new(None)];
+    /// for slot in &inventory {
+    ///     assert_eq!(slot.quantity(), slot.item_instance().map(|i| i.quant()).unwrap_or(0));
+    /// }
+    /// assert_eq!(inventory.iter().map(|s| s.quantity()).sum::<u16>(), 23);
+    /// ```
+    fn quantity(&self) -> u16 {
+        self.item_instance().map(|i| i.quant()).unwrap_or(0)
+    }
+    /// The id of the item stored by this slot, if there is one.
+    ///
+    /// ```
+    /// # use game_inventory::samples::{DefaultSlot, TORCH, TORCH_INST};
+    /// # use game_inventory::traits::{Slot, Item};
+    /// let inventory = vec![DefaultSlot::new(TORCH_INST.clone()), DefaultSlot::new(None)];
+    /// assert_eq!(inventory[0].item_id(), Some(TORCH.id()));
+    /// assert!(inventory[1].item_id().is_none());
+    /// ```
+    fn item_id(&self) -> Option<I::Id> {
+        self.item_instance().map(|i| i.item().id())
+    }
+    /// The id of the item stored by this slot as a string, for showing it in the UI.
+    ///
+    /// Works with any id type that implements `Display`.
+    /// ```
+    /// # use game_inventory::samples::{DefaultSlot, TORCH_INST};
+    /// # use game_inventory::traits::Slot;
+    /// let inventory = vec![DefaultSlot::new(TORCH_INST.clone()), DefaultSlot::new(None)];
+    /// assert_eq!(inventory[0].item_name(), Some("torch".to_owned()));
+    /// assert!(inventory[1].item_name().is_none());
+    /// ```
+    fn item_name(&self) -> Option<String>
+    where
+        I::Id: Display,
+    {
+        self.item_id().map(|id| id.to_string())
+    }
+    /// The most items this slot can hold, or `0` if the slot is empty.
+    ///
+    /// Defaults to the `max_quant` of the stored item. Override this for slots that
+    /// hold less than a full stack, the inventory helpers use it to decide how much fits.
+    /// ```
+    /// # use game_inventory::samples::{DefaultSlot, TORCH_INST};
+    /// # use game_inventory::traits::Slot;
+    /// let inventory = vec![DefaultSlot::new(TORCH_INST.clone()), DefaultSlot::new(None)];
+    /// assert_eq!(inventory[0].capacity(), 100);
+    /// assert_eq!(inventory[1].capacity(), 0);
+    /// ```
+    fn capacity(&self) -> u16 {
+        self.item_instance()
+            .map(|i| i.item().max_quant())
+            .unwrap_or(0)
+    }
+    /// The most of `item` this slot could hold, even while it is empty.
+    ///
+    /// Defaults to the `max_quant` of `item`. Slots that override `capacity` should override this
+    /// too, so the inventory helpers can check how much fits before putting anything in the slot.
+    /// ```
+    /// # use game_inventory::samples::{DefaultSlot, DefaultItem, DefaultItemInstance, TORCH};
+    /// # use game_inventory::traits::Slot;
+    /// let mut slot: DefaultSlot<DefaultItem, DefaultItemInstance<DefaultItem>> = DefaultSlot::new(None);
+    /// assert_eq!(slot.capacity_for(&TORCH), 100);
+    /// slot.capacity_override = Some(10);
+    /// assert_eq!(slot.capacity_for(&TORCH), 10);
+    /// ```
+    fn capacity_for(&self, item: &I) -> u16 {
+        item.max_quant()
+    }
+    /// Whether `item` is allowed in this slot, for slots like an ammo pouch that only take some items.
+    ///
+    /// Accepts everything by default. `add_to_inventory` and the helpers built on it skip
+    /// slots that do not accept the item.
+    /// ```
+    /// # use game_inventory::samples::{DefaultSlot, DefaultItem, DefaultItemInstance, TORCH, SWORD};
+    /// # use game_inventory::traits::{Item, Slot};
+    /// let mut slot: DefaultSlot<DefaultItem, DefaultItemInstance<DefaultItem>> = DefaultSlot::new(None);
+    /// assert!(slot.accepts(&SWORD));
+    /// slot.item_filter = Some(|item| item.stackable());
+    /// assert!(slot.accepts(&TORCH));
+    /// assert!(!slot.accepts(&SWORD));
+    /// ```
+    fn accepts(&self, _item: &I) -> bool {
+        true
+    }
+    /// Adds `amount` to the stack in this slot, up to its `capacity`, returning how many did not fit.
+    ///
+    /// Nothing is added to empty slots or slots holding an unstackable item.
+    /// ```
+    /// # use game_inventory::samples::{DefaultSlot, TORCH_INST, SWORD_INST};
+    /// # use game_inventory::traits::Slot;
+    /// let mut inventory = vec![
+    ///     DefaultSlot::new(TORCH_INST.clone()),
+    ///     DefaultSlot::new(None),
+    ///     DefaultSlot::new(SWORD_INST.clone()),
+    /// ];
+    /// assert_eq!(inventory[0].try_add_quantity(50), 0);
+    /// assert_eq!(inventory[0].try_add_quantity(50), 23);
+    /// assert_eq!(inventory[0].quantity(), 100);
+    /// assert_eq!(inventory[1].try_add_quantity(5), 5);
+    /// assert_eq!(inventory[2].try_add_quantity(1), 1);
+    /// ```
+    fn try_add_quantity(&mut self, amount: u16) -> u16 {
+        let ii = match self.item_instance() {
+            Some(ii) if ii.item().stackable() => ii,
+            _ => return amount,
+        };
+        let added = amount.min(self.capacity().saturating_sub(ii.quant()));
+        if added > 0 {
+            self.set_item_instance(&Some(II::new(ii.item(), ii.quant() + added)));
+        }
+        amount - added
+    }
+    /// How full the stack in this slot is, from `0.0` to `1.0`, for drawing a fill bar.
+    ///
+    /// Empty slots are `0.0` and slots holding an unstackable item are `1.0`.
+    /// ```
+    /// # use game_inventory::samples::{DefaultSlot, TORCH_INST, TORCH_FULL_STACK_INST, SWORD_INST};
+    /// # use game_inventory::traits::Slot;
+    /// let inventory = vec![
+    ///     DefaultSlot::new(None),
+    ///     DefaultSlot::new(TORCH_INST.clone()),
+    ///     DefaultSlot::new(TORCH_FULL_STACK_INST.clone()),
+    ///     DefaultSlot::new(SWORD_INST.clone()),
+    /// ];
+    /// let progress: Vec<f32> = inventory.iter().map(|s| s.stack_progress()).collect();
+    /// assert_eq!(progress, vec![0.0, 0.23, 1.0, 1.0]);
+    /// ```
+    #[inline]
+    fn stack_progress(&self) -> f32 {
+        match self.item_instance() {
+            None => 0.0,
+            Some(i) if !i.item().stackable() || self.capacity() == 0 => 1.0,
+            Some(i) => (i.quant() as f32 / self.capacity() as f32).clamp(0.0, 1.0),
+        }
+    }
+    /// The quantity and capacity of this slot as text, like `"23/100"`.
+    ///
+    /// Empty slots are `"--"` and slots holding an unstackable item are `"1/1"`.
+    /// ```
+    /// # use game_inventory::samples::{DefaultSlot, TORCH_INST, TORCH_FULL_STACK_INST, SWORD_INST};
+    /// # use game_inventory::traits::Slot;
+    /// let inventory = vec![
+    ///     DefaultSlot::new(None),
+    ///     DefaultSlot::new(TORCH_INST.clone()),
+    ///     DefaultSlot::new(TORCH_FULL_STACK_INST.clone()),
+    ///     DefaultSlot::new(SWORD_INST.clone()),
+    /// ];
+    /// let text: Vec<String> = inventory.iter().map(|s| s.stack_progress_text()).collect();
+    /// assert_eq!(text, vec!["--", "23/100", "100/100", "1/1"]);
+    /// ```
+    #[inline]
+    fn stack_progress_text(&self) -> String {
+        match self.item_instance() {
+            None => "--".to_owned(),
+            Some(i) if !i.item().stackable() => "1/1".to_owned(),
+            Some(i) => format!("{}/{}", i.quant(), self.capacity()),
+        }
+    }
     /// Switch out the item instance stored in this slot with some rules.
     ///
     /// The input is the item you intend to put into the slot and the output
@@ -59,6 +443,42 @@ pub trait Slot<I: Item, II: ItemInstance<I>> {
         self.set_item_instance(&res.0);
         res.1
     }
+    /// Edits the item instance stored by this slot, returning what `f` returns.
+    ///
+    /// Returns `None` without calling `f` if the slot is empty. The edited instance
+    /// is stored with `set_item_instance`, so the slot is marked as modified and its
+    /// callbacks run the same as for any other change.
+    ///
+    /// ```
+    /// # use game_inventory::samples::{DefaultSlot, TORCH_INST};
+    /// # use game_inventory::traits::{Slot, ItemInstance};
+    /// let mut inventory = vec![DefaultSlot::new(TORCH_INST.clone()), DefaultSlot::new(None)];
+    /// assert_eq!(inventory[0].modify(|i| { *i = i.clone().reduce(3).unwrap(); i.quant() }), Some(20));
+    /// assert_eq!(inventory[0].quantity(), 20);
+    /// assert!(inventory[0].modified);
+    /// assert_eq!(inventory[1].modify(|i| i.quant()), None);
+    /// ```
+    fn modify<R, F: FnOnce(&mut II) -> R>(&mut self, f: F) -> Option<R> {
+        let mut item_instance = self.item_instance()?;
+        let res = f(&mut item_instance);
+        self.set_item_instance(&Some(item_instance));
+        Some(res)
+    }
+    /// Called when an item instance is put into this slot.
+    ///
+    /// Does nothing by default. Override this to play a sound, animate the slot e.t.c.
+    /// Neither this nor `on_removed` is called when only the quantity of the item in the slot changes.
+    fn on_added(&mut self, _item_instance: &II) {}
+    /// Called when an item instance is taken out of this slot.
+    ///
+    /// Does nothing by default. When a slot goes from one item instance to another,
+    /// this is called for the old instance before `on_added` is called for the new one.
+    fn on_removed(&mut self, _item_instance: &II) {}
+    /// Called when something happens to this slot, like being hovered or selected.
+    ///
+    /// Does nothing by default. Your UI code calls this, and implementors
+    /// override it to react to the event.
+    fn emit_event(&mut self, _event: SlotEvent<II>) {}
     /// Whether the slots contents have been modified.
     fn modified(&mut self) -> bool;
     /// Sets whether the slots contents have been modified.
@@ -0,0 +1,41 @@
+use game_inventory::capture::InventoryCapture;
+use game_inventory::samples::{DefaultItem, DefaultItemInstance, DefaultSlot, TORCH_INST};
+use game_inventory::traits::Slot;
+
+type TestSlot =
+    DefaultSlot<'static, DefaultItem<'static>, DefaultItemInstance<DefaultItem<'static>>>;
+
+#[cfg(feature = "capture")]
+#[test]
+fn replay_matches_captured_inventory() {
+    use game_inventory::capture::replay;
+    use game_inventory::helpers::ItemSort;
+    use game_inventory::samples::{JUNK_INST, SWORD_INST};
+
+    let mut capture: InventoryCapture<TestSlot, _> = InventoryCapture::new(vec![
+        DefaultSlot::new(JUNK_INST.clone()),
+        DefaultSlot::new(None),
+        DefaultSlot::new(None),
+    ]);
+    capture.add_item(TORCH_INST.clone().unwrap());
+    capture.add_item(SWORD_INST.clone().unwrap());
+    capture.move_slot(2, 1).unwrap_err();
+    assert!(capture.move_slot(0, 5).is_err());
+    capture.remove_item(JUNK_INST.clone().unwrap());
+    capture.sort(ItemSort::Id);
+
+    let replayed: Vec<TestSlot> = replay(capture.ops());
+    let expected: Vec<_> = capture.iter().map(|s| s.item_instance()).collect();
+    let actual: Vec<_> = replayed.iter().map(|s| s.item_instance()).collect();
+    assert_eq!(actual, expected);
+}
+
+#[cfg(not(feature = "capture"))]
+#[test]
+fn nothing_recorded_without_feature() {
+    let mut capture: InventoryCapture<TestSlot, _> =
+        InventoryCapture::new(vec![DefaultSlot::new(None)]);
+    capture.add_item(TORCH_INST.clone().unwrap());
+    assert!(capture.ops().is_empty());
+    assert_eq!(capture[0].quantity(), 23);
+}
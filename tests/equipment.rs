@@ -0,0 +1,126 @@
+use game_inventory::equipment::{swap_equipment, EquipSlot, EquipmentSet};
+use game_inventory::samples::DefaultSlot;
+use game_inventory::traits::{Item, ItemInstance, ItemValue, ItemWeight, Slot};
+use std::sync::Arc;
+
+#[derive(Debug, Clone)]
+struct Gear {
+    name: &'static str,
+    max_quantity: u16,
+    weight: f32,
+    value: u64,
+}
+
+impl Item for Gear {
+    type Id = &'static str;
+    fn stackable(&self) -> bool {
+        self.max_quantity > 1
+    }
+    fn max_quant(&self) -> u16 {
+        self.max_quantity
+    }
+    fn id(&self) -> &'static str {
+        self.name
+    }
+}
+
+impl ItemWeight for Gear {
+    fn weight(&self) -> f32 {
+        self.weight
+    }
+}
+
+impl ItemValue for Gear {
+    fn value(&self) -> u64 {
+        self.value
+    }
+}
+
+#[derive(Debug, Clone)]
+struct GearInstance(Arc<Gear>, u16);
+
+impl ItemInstance<Gear> for GearInstance {
+    fn quant(&self) -> u16 {
+        self.1
+    }
+    fn item(&self) -> Arc<Gear> {
+        self.0.clone()
+    }
+    fn new(item: Arc<Gear>, quantity: u16) -> Self {
+        GearInstance(item, quantity)
+    }
+}
+
+type GearSlot = DefaultSlot<'static, Gear, GearInstance>;
+
+fn gear(
+    name: &'static str,
+    max_quantity: u16,
+    weight: f32,
+    value: u64,
+    quant: u16,
+) -> GearInstance {
+    GearInstance::new(
+        Arc::new(Gear {
+            name,
+            max_quantity,
+            weight,
+            value,
+        }),
+        quant,
+    )
+}
+
+#[test]
+fn totals_count_stacks() {
+    let mut set: EquipmentSet<GearSlot> =
+        EquipmentSet::new([EquipSlot::Head, EquipSlot::MainHand, EquipSlot::Accessory]);
+    let mut inventory: Vec<GearSlot> = vec![];
+    swap_equipment(
+        &mut set,
+        &mut inventory,
+        EquipSlot::Head,
+        gear("helmet", 0, 2.5, 40, 0),
+    );
+    swap_equipment(
+        &mut set,
+        &mut inventory,
+        EquipSlot::Accessory,
+        gear("ring", 10, 0.5, 5, 4),
+    );
+    assert_eq!(set.total_weight(), 4.5);
+    assert_eq!(set.total_value(), 60);
+    assert_eq!(set.missing_slots(), vec![EquipSlot::MainHand]);
+}
+
+#[test]
+fn displaced_item_kept_when_inventory_full() {
+    let mut set: EquipmentSet<GearSlot> = EquipmentSet::new([EquipSlot::MainHand]);
+    let mut inventory: Vec<GearSlot> = vec![DefaultSlot::new(Some(gear("rock", 0, 1.0, 0, 0)))];
+    assert!(swap_equipment(
+        &mut set,
+        &mut inventory,
+        EquipSlot::MainHand,
+        gear("axe", 0, 3.0, 9, 0)
+    )
+    .is_none());
+    let displaced = swap_equipment(
+        &mut set,
+        &mut inventory,
+        EquipSlot::MainHand,
+        gear("mace", 0, 4.0, 9, 0),
+    );
+    assert_eq!(displaced.unwrap().item().id(), "axe");
+    assert_eq!(
+        set.get(EquipSlot::MainHand).unwrap().item_id(),
+        Some("mace")
+    );
+
+    let rejected = swap_equipment(
+        &mut set,
+        &mut inventory,
+        EquipSlot::Head,
+        gear("hat", 0, 0.1, 1, 0),
+    );
+    assert_eq!(rejected.unwrap().item().id(), "hat");
+}
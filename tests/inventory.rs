@@ -0,0 +1,569 @@
+use game_inventory::errors::{CraftError, DuplicateError, InventoryError, ValidationError};
+use game_inventory::helpers::{
+    add_to_inventory, batch_add_to_inventory, can_add_item_to_inventory, can_craft, craft_item,
+    empty_quant_in_inventory, inventory_checksum, inventory_complement, inventory_difference,
+    inventory_intersection, inventory_union, item_total_quantity, item_type_counts,
+    remove_from_inventory, reorder_inventory, validate_inventory, validate_inventory_strict,
+    verify_checksum, ItemSort,
+};
+use game_inventory::inventory::{
+    ConstrainedInventory, FixedInventory, Inventory, InventoryConstraints, SortedInsertInventory,
+};
+use game_inventory::samples::{
+    DefaultItem, DefaultItemInstance, DefaultSlot, JUNK, SWORD_INST, TORCH, TORCH_FULL_STACK_INST,
+    TORCH_INST, TORCH_RECIPE,
+};
+use game_inventory::traits::{Item, ItemInstance, Slot};
+use std::sync::Arc;
+
+type TestSlot =
+    DefaultSlot<'static, DefaultItem<'static>, DefaultItemInstance<DefaultItem<'static>>>;
+
+#[test]
+fn fixed_inventory_sizes() {
+    let mut none: FixedInventory<TestSlot, 0> = FixedInventory::empty();
+    assert!(add_to_inventory(&mut none, TORCH_INST.clone().unwrap()).is_some());
+
+    let mut one: FixedInventory<TestSlot, 1> = FixedInventory::empty();
+    assert!(add_to_inventory(&mut one, TORCH_INST.clone().unwrap()).is_none());
+    assert_eq!(one[0].quantity(), 23);
+
+    let mut many: FixedInventory<TestSlot, 64> = FixedInventory::empty();
+    assert!(add_to_inventory(&mut many, TORCH_INST.clone().unwrap()).is_none());
+    assert_eq!(empty_quant_in_inventory(&many), 63);
+}
+
+#[test]
+fn fixed_inventory_is_copy() {
+    fn assert_copy<T: Copy>(_: T) {}
+    assert_copy(FixedInventory::new([0u8; 4]));
+}
+
+#[test]
+fn capacity_override_limits_stack() {
+    let mut limited: TestSlot = DefaultSlot::new(None);
+    limited.capacity_override = Some(10);
+    let mut inventory = vec![limited, DefaultSlot::new(None)];
+    let torches = DefaultItemInstance::new(Arc::new(TORCH.clone()), 50).unwrap();
+    assert!(add_to_inventory(&mut inventory, torches.clone()).is_none());
+    assert_eq!(inventory[0].quantity(), 10);
+    assert_eq!(inventory[0].capacity(), 10);
+    assert_eq!(inventory[1].quantity(), 40);
+
+    assert!(add_to_inventory(&mut inventory, torches.clone()).is_none());
+    assert_eq!(inventory[0].quantity(), 10);
+    assert_eq!(inventory[1].quantity(), 90);
+
+    let rest = add_to_inventory(&mut inventory, torches).unwrap();
+    assert_eq!(inventory[0].quantity(), 10);
+    assert_eq!(inventory[1].quantity(), 100);
+    assert_eq!(rest.quant(), 40);
+}
+
+#[test]
+fn craft_item_without_ingredients() {
+    let mut inventory: Vec<TestSlot> = vec![
+        DefaultSlot::new(Some(
+            DefaultItemInstance::new(Arc::new(JUNK.clone()), 9).unwrap(),
+        )),
+        DefaultSlot::new(None),
+    ];
+    let mut output: Vec<TestSlot> = vec![DefaultSlot::new(None)];
+    assert_eq!(
+        craft_item(&mut inventory, &TORCH_RECIPE, &mut output),
+        Err(CraftError::InsufficientIngredients)
+    );
+    assert_eq!(inventory[0].quantity(), 9);
+    assert!(output[0].item_instance().is_none());
+}
+
+#[test]
+fn checksum_changes_with_any_slot() {
+    let inventory: Vec<TestSlot> = vec![
+        DefaultSlot::new(TORCH_INST.clone()),
+        DefaultSlot::new(None),
+        DefaultSlot::new(Some(
+            DefaultItemInstance::new(Arc::new(JUNK.clone()), 7).unwrap(),
+        )),
+    ];
+    let checksum = inventory_checksum(&inventory);
+    assert_eq!(inventory_checksum(&inventory.clone()), checksum);
+    for index in 0..inventory.len() {
+        let mut changed = inventory.clone();
+        let quant = changed[index].quantity();
+        changed[index].set_item_instance(&Some(DefaultItemInstance::new_unchecked(
+            Arc::new(TORCH.clone()),
+            quant ^ 1,
+        )));
+        assert!(!verify_checksum(&changed, checksum), "slot {}", index);
+
+        let mut cleared = inventory.clone();
+        cleared[index].set_item_instance(&None);
+        if inventory[index].item_instance().is_some() {
+            assert!(!verify_checksum(&cleared, checksum), "slot {}", index);
+        }
+    }
+}
+
+#[test]
+fn batch_add_matches_sequential_add() {
+    let torches = |q| DefaultItemInstance::new(Arc::new(TORCH.clone()), q).unwrap();
+    let junk = |q| DefaultItemInstance::new(Arc::new(JUNK.clone()), q).unwrap();
+    // Grouped by item, so adding one at a time fills the slots in the same order.
+    let items = vec![
+        torches(60),
+        torches(70),
+        torches(90),
+        junk(30),
+        junk(95),
+        SWORD_INST.clone().unwrap(),
+    ];
+    let start = || -> Vec<TestSlot> {
+        vec![
+            DefaultSlot::new(Some(torches(50))),
+            DefaultSlot::new(None),
+            DefaultSlot::new(None),
+            DefaultSlot::new(None),
+        ]
+    };
+
+    let mut sequential = start();
+    let sequential_overflow: Vec<_> = items
+        .iter()
+        .filter_map(|i| add_to_inventory(&mut sequential, i.clone()))
+        .collect();
+    let mut batch = start();
+    let batch_overflow = batch_add_to_inventory(&mut batch, items);
+
+    assert_eq!(item_type_counts(&batch), item_type_counts(&sequential));
+    fn overflow_total(o: &[DefaultItemInstance<DefaultItem<'static>>]) -> Vec<(&'static str, u16)> {
+        let mut o: Vec<_> = o.iter().map(|i| (i.item().id(), i.quant())).collect();
+        o.sort();
+        o
+    }
+    assert_eq!(
+        overflow_total(&batch_overflow),
+        overflow_total(&sequential_overflow)
+    );
+}
+
+#[test]
+fn add_to_inventory_keeps_total_quantity() {
+    let torches = |q| DefaultItemInstance::new(Arc::new(TORCH.clone()), q).unwrap();
+    let mut inventory: Vec<TestSlot> = vec![
+        DefaultSlot::new(Some(torches(40))),
+        DefaultSlot::new(SWORD_INST.clone()),
+        DefaultSlot::new(None),
+        DefaultSlot::new(Some(torches(95))),
+        DefaultSlot::new(None),
+    ];
+    for added in [30, 100, 7, 100, 100, 65] {
+        let before = item_total_quantity(&inventory, TORCH.id());
+        let overflow = add_to_inventory(&mut inventory, torches(added)).map_or(0, |o| o.quant());
+        assert_eq!(
+            before + added as u32 - overflow as u32,
+            item_total_quantity(&inventory, TORCH.id())
+        );
+    }
+    assert_eq!(item_total_quantity(&inventory, TORCH.id()), 400);
+}
+
+#[test]
+fn reorder_round_trips_with_argsort() {
+    let mut inventory: Vec<TestSlot> = vec![
+        DefaultSlot::new(TORCH_INST.clone()),
+        DefaultSlot::new(Some(DefaultItemInstance::new_unchecked(
+            Arc::new(JUNK.clone()),
+            5,
+        ))),
+        DefaultSlot::new(None),
+        DefaultSlot::new(SWORD_INST.clone()),
+        DefaultSlot::new(Some(DefaultItemInstance::new_unchecked(
+            Arc::new(TORCH.clone()),
+            7,
+        ))),
+    ];
+    let before: Vec<_> = inventory.iter().map(|s| s.item_instance()).collect();
+    let order = [3, 0, 4, 2, 1];
+    reorder_inventory(&mut inventory, &order.map(Some)).unwrap();
+    for (i, &from) in order.iter().enumerate() {
+        assert_eq!(inventory[i].item_instance(), before[from]);
+    }
+
+    let mut argsort = [0; 5];
+    for (i, &from) in order.iter().enumerate() {
+        argsort[from] = i;
+    }
+    reorder_inventory(&mut inventory, &argsort.map(Some)).unwrap();
+    let after: Vec<_> = inventory.iter().map(|s| s.item_instance()).collect();
+    assert_eq!(after, before);
+}
+
+#[test]
+fn add_to_inventory_with_option_slots() {
+    let mut slots: Vec<TestSlot> = (0..3).map(|_| DefaultSlot::new(None)).collect();
+    let mut options: Vec<Option<DefaultItemInstance<DefaultItem<'static>>>> = vec![None; 3];
+    for ii in [TORCH_INST.clone(), SWORD_INST.clone(), TORCH_INST.clone()] {
+        assert!(add_to_inventory(&mut slots, ii.clone().unwrap()).is_none());
+        assert!(add_to_inventory(&mut options, ii.unwrap()).is_none());
+    }
+    let from_slots: Vec<_> = slots.iter().map(|s| s.item_instance()).collect();
+    let from_options: Vec<_> = options.iter().map(|s| s.item_instance()).collect();
+    assert_eq!(from_slots, from_options);
+    assert_eq!(options[0].quantity(), 46);
+    assert!(options[2].is_empty());
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct EnchantedItem {
+    enchantment: &'static str,
+}
+
+impl Item for EnchantedItem {
+    type Id = &'static str;
+    fn stackable(&self) -> bool {
+        true
+    }
+    fn max_quant(&self) -> u16 {
+        10
+    }
+    fn id(&self) -> &'static str {
+        "arrow"
+    }
+    fn stackable_with<II: ItemInstance<Self>>(&self, other: &II) -> bool {
+        self.id() == other.item().id() && self.enchantment == other.item().enchantment
+    }
+}
+
+#[test]
+fn add_to_inventory_uses_stackable_with() {
+    let fire = Arc::new(EnchantedItem {
+        enchantment: "fire",
+    });
+    let frost = Arc::new(EnchantedItem {
+        enchantment: "frost",
+    });
+    let mut inventory: Vec<DefaultSlot<EnchantedItem, DefaultItemInstance<EnchantedItem>>> =
+        (0..2).map(|_| DefaultSlot::new(None)).collect();
+    add_to_inventory(
+        &mut inventory,
+        DefaultItemInstance::new(fire.clone(), 3).unwrap(),
+    );
+    add_to_inventory(&mut inventory, DefaultItemInstance::new(frost, 3).unwrap());
+    add_to_inventory(&mut inventory, DefaultItemInstance::new(fire, 3).unwrap());
+    assert_eq!(inventory[0].quantity(), 6);
+    assert_eq!(inventory[1].quantity(), 3);
+    assert_eq!(
+        inventory[1].item_instance().unwrap().item().enchantment,
+        "frost"
+    );
+}
+
+#[test]
+fn remove_from_inventory_uses_stackable_with() {
+    let fire = Arc::new(EnchantedItem {
+        enchantment: "fire",
+    });
+    let frost = Arc::new(EnchantedItem {
+        enchantment: "frost",
+    });
+    let mut inventory: Vec<DefaultSlot<EnchantedItem, DefaultItemInstance<EnchantedItem>>> = vec![
+        DefaultSlot::new(Some(DefaultItemInstance::new(frost.clone(), 5).unwrap())),
+        DefaultSlot::new(Some(DefaultItemInstance::new(fire.clone(), 5).unwrap())),
+    ];
+    assert!(remove_from_inventory(
+        &mut inventory,
+        DefaultItemInstance::new(fire.clone(), 4).unwrap()
+    )
+    .is_none());
+    assert_eq!(inventory[0].quantity(), 5);
+    assert_eq!(inventory[1].quantity(), 1);
+
+    let left_over =
+        remove_from_inventory(&mut inventory, DefaultItemInstance::new(fire, 3).unwrap()).unwrap();
+    assert_eq!(left_over.quant(), 2);
+    assert!(inventory[1].is_empty());
+    assert_eq!(inventory[0].item_instance().unwrap().item(), frost);
+    assert_eq!(inventory[0].quantity(), 5);
+}
+
+#[test]
+fn inventory_difference_with_more_than_u16_max() {
+    let inventory: Vec<TestSlot> = (0..700)
+        .map(|_| DefaultSlot::new(TORCH_FULL_STACK_INST.clone()))
+        .collect();
+    assert!(inventory_difference(&inventory, &[(TORCH.id(), 100)]).is_empty());
+    assert_eq!(
+        inventory_difference(&inventory, &[(TORCH.id(), 100), (JUNK.id(), 5)]),
+        vec![(JUNK.id(), 5)]
+    );
+}
+
+#[test]
+fn can_craft_with_more_than_u16_max() {
+    let inventory: Vec<TestSlot> = (0..700)
+        .map(|_| {
+            DefaultSlot::new(Some(DefaultItemInstance::new_unchecked(
+                Arc::new(JUNK.clone()),
+                100,
+            )))
+        })
+        .collect();
+    assert!(can_craft(&inventory, &TORCH_RECIPE));
+}
+
+#[test]
+fn inventory_set_operations_with_more_than_u16_max() {
+    let a: Vec<TestSlot> = (0..700)
+        .map(|_| DefaultSlot::new(TORCH_FULL_STACK_INST.clone()))
+        .collect();
+    let b: Vec<TestSlot> = vec![DefaultSlot::new(TORCH_INST.clone())];
+    assert_eq!(inventory_intersection(&a, &b), vec![(TORCH.id(), 23)]);
+    assert_eq!(inventory_union(&a, &b), vec![(TORCH.id(), 70_000)]);
+    assert_eq!(inventory_complement(&a, &b), vec![(TORCH.id(), 69_977)]);
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct ZeroStackItem;
+
+impl Item for ZeroStackItem {
+    type Id = &'static str;
+    fn stackable(&self) -> bool {
+        true
+    }
+    fn max_quant(&self) -> u16 {
+        0
+    }
+    fn id(&self) -> &'static str {
+        "zero"
+    }
+}
+
+#[test]
+fn stackable_item_with_zero_max_quant() {
+    assert_eq!(ZeroStackItem.quantity_range(), 1..=1);
+    let item = Arc::new(ZeroStackItem);
+    assert_eq!(
+        DefaultItemInstance::new_unchecked(item.clone(), 5)
+            .clamp_quantity()
+            .quant(),
+        1
+    );
+    assert!(DefaultItemInstance::new(item.clone(), 1).is_ok());
+    assert!(DefaultItemInstance::new(item, 2).is_err());
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct InvertedRangeItem;
+
+impl Item for InvertedRangeItem {
+    type Id = &'static str;
+    fn stackable(&self) -> bool {
+        true
+    }
+    fn max_quant(&self) -> u16 {
+        10
+    }
+    fn id(&self) -> &'static str {
+        "inverted"
+    }
+    #[allow(clippy::reversed_empty_ranges)]
+    fn quantity_range(&self) -> std::ops::RangeInclusive<u16> {
+        5..=2
+    }
+}
+
+#[test]
+fn clamp_quantity_with_empty_range() {
+    let ii = DefaultItemInstance::new_unchecked(Arc::new(InvertedRangeItem), 7);
+    assert_eq!(ii.clamp_quantity().quant(), 2);
+}
+
+#[test]
+fn batch_add_consolidates_overflow() {
+    let mut inventory: Vec<TestSlot> = (0..2).map(|_| DefaultSlot::new(None)).collect();
+    let torches = |q| DefaultItemInstance::new(Arc::new(TORCH.clone()), q).unwrap();
+    let overflow = batch_add_to_inventory(
+        &mut inventory,
+        vec![torches(100), torches(100), torches(100), torches(50)],
+    );
+    assert_eq!(overflow.len(), 1);
+    assert_eq!(overflow[0].quant(), 150);
+}
+
+#[test]
+fn batch_add_with_zero_max_quant() {
+    let mut inventory: Vec<DefaultSlot<ZeroStackItem, DefaultItemInstance<ZeroStackItem>>> =
+        (0..2).map(|_| DefaultSlot::new(None)).collect();
+    let item = Arc::new(ZeroStackItem);
+    let overflow = batch_add_to_inventory(
+        &mut inventory,
+        vec![DefaultItemInstance::new_unchecked(item, 3)],
+    );
+    // The slots have no room for an item with a max_quant of 0, but the loop still ends.
+    assert!(inventory.iter().all(|s| s.is_empty()));
+    assert_eq!(overflow.len(), 1);
+    assert_eq!(overflow[0].quant(), 3);
+}
+
+#[test]
+fn batch_add_groups_by_stackable_with() {
+    let fire = Arc::new(EnchantedItem {
+        enchantment: "fire",
+    });
+    let frost = Arc::new(EnchantedItem {
+        enchantment: "frost",
+    });
+    let mut inventory: Vec<DefaultSlot<EnchantedItem, DefaultItemInstance<EnchantedItem>>> =
+        (0..3).map(|_| DefaultSlot::new(None)).collect();
+    let overflow = batch_add_to_inventory(
+        &mut inventory,
+        vec![
+            DefaultItemInstance::new(fire.clone(), 3).unwrap(),
+            DefaultItemInstance::new(frost, 4).unwrap(),
+            DefaultItemInstance::new(fire, 2).unwrap(),
+        ],
+    );
+    assert!(overflow.is_empty());
+    let stacks: Vec<_> = inventory
+        .iter()
+        .map(|s| s.item_instance().map(|i| (i.item().enchantment, i.quant())))
+        .collect();
+    assert_eq!(stacks, vec![Some(("fire", 5)), Some(("frost", 4)), None]);
+}
+
+#[test]
+fn add_skips_slots_that_do_not_accept_the_item() {
+    let mut inventory: Vec<TestSlot> = (0..2).map(|_| DefaultSlot::new(None)).collect();
+    inventory[0].item_filter = Some(|item| !item.stackable());
+    assert!(add_to_inventory(&mut inventory, TORCH_INST.clone().unwrap()).is_none());
+    assert!(inventory[0].is_empty());
+    assert_eq!(inventory[1].quantity(), 23);
+}
+
+#[test]
+fn constrained_inventory_checks_before_changing() {
+    let mut slot: TestSlot = DefaultSlot::new(TORCH_INST.clone());
+    slot.capacity_override = Some(10);
+    let mut inventory = ConstrainedInventory::new(
+        vec![slot, DefaultSlot::new(None)],
+        InventoryConstraints {
+            validate_on_mutate: true,
+            ..Default::default()
+        },
+    );
+    let err = inventory.add_item(SWORD_INST.clone().unwrap()).unwrap_err();
+    assert!(matches!(err, InventoryError::Quantity(_)));
+    assert!(inventory[1].is_empty());
+}
+
+#[test]
+fn inventory_count_item_past_u16() {
+    let inventory: Inventory<TestSlot> = Inventory::new(
+        (0..700)
+            .map(|_| DefaultSlot::new(TORCH_FULL_STACK_INST.clone()))
+            .collect(),
+    );
+    assert_eq!(inventory.count_item(TORCH.id()), 70_000);
+}
+
+#[test]
+fn sorted_insert_keeps_topped_up_stack_in_place() {
+    let torch = TORCH_INST.clone().unwrap().item();
+    let mut inventory: SortedInsertInventory<TestSlot> = SortedInsertInventory::from_slots(
+        vec![
+            DefaultSlot::new(Some(DefaultItemInstance::new(torch.clone(), 50).unwrap())),
+            DefaultSlot::new(TORCH_FULL_STACK_INST.clone()),
+        ],
+        ItemSort::Id,
+    );
+    assert!(inventory
+        .add_item(DefaultItemInstance::new(torch, 10).unwrap())
+        .is_none());
+    let quantities: Vec<_> = inventory.iter().map(|s| s.quantity()).collect();
+    assert_eq!(quantities, vec![60, 100]);
+}
+
+#[test]
+fn constrained_inventory_without_weights() {
+    let fire = Arc::new(EnchantedItem {
+        enchantment: "fire",
+    });
+    let mut inventory: ConstrainedInventory<
+        DefaultSlot<EnchantedItem, DefaultItemInstance<EnchantedItem>>,
+    > = ConstrainedInventory::new(
+        vec![DefaultSlot::new(None)],
+        InventoryConstraints {
+            max_slots: Some(1),
+            ..Default::default()
+        },
+    );
+    let arrows = DefaultItemInstance::new(fire, 10).unwrap();
+    assert!(inventory.add_item(arrows.clone()).unwrap().is_none());
+    assert_eq!(
+        inventory.add_item(arrows).unwrap_err(),
+        InventoryError::InventoryFull
+    );
+}
+
+#[test]
+fn can_add_item_respects_filters_and_capacity() {
+    let mut inventory: Vec<TestSlot> = vec![DefaultSlot::new(None)];
+    inventory[0].item_filter = Some(|item| !item.stackable());
+    assert!(!can_add_item_to_inventory(
+        &inventory,
+        TORCH_INST.clone().unwrap()
+    ));
+    assert!(add_to_inventory(&mut inventory, TORCH_INST.clone().unwrap()).is_some());
+    assert!(can_add_item_to_inventory(
+        &inventory,
+        SWORD_INST.clone().unwrap()
+    ));
+
+    inventory[0].item_filter = Some(|item| item.stackable());
+    assert!(!can_add_item_to_inventory(
+        &inventory,
+        SWORD_INST.clone().unwrap()
+    ));
+    inventory[0].capacity_override = Some(10);
+    assert!(!can_add_item_to_inventory(
+        &inventory,
+        TORCH_INST.clone().unwrap()
+    ));
+    inventory.push(DefaultSlot::new(None));
+    assert!(can_add_item_to_inventory(
+        &inventory,
+        TORCH_INST.clone().unwrap()
+    ));
+    assert!(add_to_inventory(&mut inventory, TORCH_INST.clone().unwrap()).is_none());
+}
+
+#[test]
+fn validate_inventory_strict_finds_duplicates() {
+    let mut inventory: Vec<TestSlot> = vec![
+        DefaultSlot::new(SWORD_INST.clone()),
+        DefaultSlot::new(TORCH_INST.clone()),
+        DefaultSlot::new(SWORD_INST.clone()),
+    ];
+    assert!(validate_inventory(&inventory).is_ok());
+    let errors = validate_inventory_strict(&inventory);
+    assert!(!errors.is_empty());
+    assert_eq!(
+        errors,
+        vec![ValidationError::Duplicate(DuplicateError {
+            item_id: "sword",
+            slot_indices: vec![0, 2],
+        })]
+    );
+
+    inventory[1].capacity_override = Some(10);
+    let errors = validate_inventory_strict(&inventory);
+    assert_eq!(errors.len(), 2);
+    assert!(matches!(
+        errors[0],
+        ValidationError::Slot {
+            index: 1,
+            error: InventoryError::Quantity(_)
+        }
+    ));
+}
@@ -0,0 +1,74 @@
+use game_inventory::observer::{InventoryBroadcast, InventoryObserver};
+use game_inventory::samples::{
+    DefaultItem, DefaultItemInstance, DefaultSlot, JUNK_INST, TORCH_INST,
+};
+use game_inventory::slot_management::SlotEvent;
+use game_inventory::traits::Slot;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+type TestInstance = DefaultItemInstance<DefaultItem<'static>>;
+
+#[derive(Clone, Default)]
+struct Recorder(Rc<RefCell<Vec<String>>>);
+
+impl InventoryObserver<TestInstance> for Recorder {
+    fn notify(&mut self, event: &SlotEvent<TestInstance>) {
+        let name = match event {
+            SlotEvent::Selected => "selected",
+            SlotEvent::QuantityChanged { .. } => "quantity changed",
+            SlotEvent::ItemSwapped { .. } => "item swapped",
+            _ => "other",
+        };
+        self.0.borrow_mut().push(name.to_owned());
+    }
+}
+
+#[test]
+fn broadcast_reaches_every_observer() {
+    let recorders = [
+        Recorder::default(),
+        Recorder::default(),
+        Recorder::default(),
+    ];
+    let mut broadcast = InventoryBroadcast::new();
+    let ids: Vec<_> = recorders
+        .iter()
+        .map(|r| broadcast.add_observer(Box::new(r.clone())))
+        .collect();
+    assert_eq!(broadcast.len(), 3);
+
+    let mut slot: DefaultSlot<DefaultItem, TestInstance> = DefaultSlot::new(TORCH_INST.clone());
+    slot.set_broadcast(broadcast);
+    slot.emit_event(SlotEvent::Selected);
+    slot.set_item_instance(&Some(TORCH_INST.clone().unwrap().reduce(3).unwrap()));
+    slot.set_item_instance(&JUNK_INST.clone());
+
+    for recorder in &recorders {
+        assert_eq!(
+            *recorder.0.borrow(),
+            vec!["selected", "quantity changed", "item swapped"]
+        );
+    }
+    assert_eq!(ids, vec![0, 1, 2]);
+}
+
+#[test]
+fn removed_observers_stop_receiving_events() {
+    let recorders = [
+        Recorder::default(),
+        Recorder::default(),
+        Recorder::default(),
+    ];
+    let mut broadcast: InventoryBroadcast<TestInstance> = InventoryBroadcast::new();
+    let ids: Vec<_> = recorders
+        .iter()
+        .map(|r| broadcast.add_observer(Box::new(r.clone())))
+        .collect();
+    assert!(broadcast.remove_observer(ids[1]));
+    assert!(!broadcast.remove_observer(ids[1]));
+
+    broadcast.notify(&SlotEvent::Selected);
+    let counts: Vec<_> = recorders.iter().map(|r| r.0.borrow().len()).collect();
+    assert_eq!(counts, vec![1, 0, 1]);
+}
@@ -0,0 +1,37 @@
+use game_inventory::pool::InventoryPool;
+use game_inventory::samples::TORCH;
+use game_inventory::traits::ItemInstance;
+use std::sync::Arc;
+
+#[test]
+fn instances_return_to_pool_once() {
+    let mut pool = InventoryPool::new(Arc::new(TORCH.clone()), 4);
+    let held: Vec<_> = (1..=6).map(|q| pool.acquire(q).unwrap()).collect();
+    assert_eq!(pool.available(), 0);
+    assert_eq!(
+        held.iter().map(|i| i.quant()).collect::<Vec<_>>(),
+        vec![1, 2, 3, 4, 5, 6]
+    );
+    drop(held);
+    assert_eq!(pool.available(), 6);
+
+    let mut reused = pool.acquire(7).unwrap();
+    *reused = reused.clone().with_quantity(8).unwrap();
+    assert_eq!(reused.quant(), 8);
+    assert_eq!(pool.available(), 5);
+    drop(reused);
+    assert_eq!(pool.available(), 6);
+
+    let kept = pool.acquire(9).unwrap().into_inner();
+    assert_eq!(kept.quant(), 9);
+    assert_eq!(pool.available(), 5);
+}
+
+#[test]
+fn acquire_rejects_invalid_quantities() {
+    let mut pool = InventoryPool::new(Arc::new(TORCH.clone()), 2);
+    assert!(pool.acquire(0).is_err());
+    assert!(pool.acquire(101).is_err());
+    assert_eq!(pool.available(), 2);
+    assert_eq!(pool.acquire(100).unwrap().quant(), 100);
+}
@@ -1,12 +1,20 @@
-use game_inventory::samples::{DefaultItem, DefaultItemInstance, DefaultSlot, TORCH_INST};
-use game_inventory::traits::Slot;
+use game_inventory::helpers::{add_to_inventory, SlotEvent};
+use game_inventory::samples::{
+    DefaultItem, DefaultItemInstance, DefaultSlot, JUNK_INST, SWORD_INST, TORCH_FULL_STACK_INST,
+    TORCH_INST,
+};
+use game_inventory::traits::{Item, ItemInstance, Slot};
+use std::{cell::RefCell, rc::Rc, sync::Arc};
+
+type TestSlot =
+    DefaultSlot<'static, DefaultItem<'static>, DefaultItemInstance<DefaultItem<'static>>>;
 
 #[test]
 fn set() {
     let mut slot = DefaultSlot::new(None);
-    assert_eq!(slot.modified, false);
+    assert!(!slot.modified);
     slot.set_item_instance(&TORCH_INST);
-    assert_eq!(slot.modified, true);
+    assert!(slot.modified);
 }
 #[test]
 fn swap() {
@@ -15,7 +23,136 @@ fn swap() {
         DefaultItem<'static>,
         DefaultItemInstance<DefaultItem<'static>>,
     >>::new(None);
-    assert_eq!(slot.modified, false);
+    assert!(!slot.modified);
     slot.transfer(TORCH_INST.clone(), "");
-    assert_eq!(slot.modified, true);
+    assert!(slot.modified);
+}
+#[test]
+fn added_and_removed_handlers() {
+    let events = Rc::new(RefCell::new(Vec::new()));
+    let mut slot = DefaultSlot::new(TORCH_INST.clone());
+    let added = events.clone();
+    slot.added_handler = Some(Box::new(move |i: &DefaultItemInstance<DefaultItem>| {
+        added.borrow_mut().push(format!("added {}", i.item().id()))
+    }));
+    let removed = events.clone();
+    slot.removed_handler = Some(Box::new(move |i: &DefaultItemInstance<DefaultItem>| {
+        removed
+            .borrow_mut()
+            .push(format!("removed {}", i.item().id()))
+    }));
+    slot.try_add_quantity(2);
+    assert_eq!(slot.quantity(), 25);
+    slot.set_item_instance(&JUNK_INST);
+    slot.set_item_instance(&None);
+    assert_eq!(
+        *events.borrow(),
+        vec!["removed torch", "added junk", "removed junk"]
+    );
+}
+#[test]
+fn slot_events() {
+    let events = Rc::new(RefCell::new(Vec::new()));
+    let mut slot = DefaultSlot::new(TORCH_INST.clone());
+    let handled = events.clone();
+    slot.event_handler = Some(Box::new(
+        move |e: SlotEvent<DefaultItemInstance<DefaultItem>>| {
+            handled.borrow_mut().push(match e {
+                SlotEvent::Hovered => "hovered".to_owned(),
+                SlotEvent::QuantityChanged { old, new } => format!("{} -> {}", old, new),
+                SlotEvent::ItemSwapped { old, new } => {
+                    format!("{} -> {}", old.item().id(), new.item().id())
+                }
+                _ => "other".to_owned(),
+            })
+        },
+    ));
+    slot.emit_event(SlotEvent::Hovered);
+    slot.set_item_instance(&TORCH_FULL_STACK_INST);
+    slot.set_item_instance(&JUNK_INST);
+    slot.set_item_instance(&None);
+    assert_eq!(
+        *events.borrow(),
+        vec!["hovered", "23 -> 100", "torch -> junk"]
+    );
+}
+
+#[test]
+fn display_name_stackable() {
+    let slot: TestSlot = DefaultSlot::new(TORCH_INST.clone());
+    assert_eq!(slot.display_name(), "torch x23 (tool)");
+    assert_eq!(slot.to_string(), "torch x23 (tool)");
+}
+
+#[test]
+fn display_name_unstackable() {
+    let slot: TestSlot = DefaultSlot::new(SWORD_INST.clone());
+    assert_eq!(slot.display_name(), "sword (weapon)");
+}
+
+#[test]
+fn display_name_empty() {
+    let slot: TestSlot = DefaultSlot::new(None);
+    assert_eq!(slot.display_name(), "[Empty]");
+}
+
+#[test]
+fn display_name_without_category() {
+    let item = DefaultItem::new("rock", 10, "");
+    let slot: TestSlot =
+        DefaultSlot::new(Some(DefaultItemInstance::new(Arc::new(item), 4).unwrap()));
+    assert_eq!(slot.display_name(), "rock x4");
+}
+
+#[derive(Debug, Clone)]
+struct NumberedItem(u32);
+
+impl Item for NumberedItem {
+    type Id = u32;
+    fn stackable(&self) -> bool {
+        true
+    }
+    fn max_quant(&self) -> u16 {
+        10
+    }
+    fn id(&self) -> u32 {
+        self.0
+    }
+}
+
+#[test]
+fn item_name_with_numeric_ids() {
+    let mut slot: DefaultSlot<NumberedItem, DefaultItemInstance<NumberedItem>> =
+        DefaultSlot::new(None);
+    assert!(slot.item_name().is_none());
+    slot.set_item_instance(&Some(
+        DefaultItemInstance::new(Arc::new(NumberedItem(42)), 3).unwrap(),
+    ));
+    assert_eq!(slot.item_name(), Some("42".to_owned()));
+}
+
+#[test]
+fn oversized_add_sets_slot_once() {
+    let events = Rc::new(RefCell::new(Vec::new()));
+    let mut inventory: Vec<TestSlot> = vec![DefaultSlot::new(None)];
+    inventory[0].capacity_override = Some(100);
+    let added = events.clone();
+    inventory[0].added_handler = Some(Box::new(move |i: &DefaultItemInstance<DefaultItem>| {
+        added.borrow_mut().push(format!("added {}", i.quant()))
+    }));
+    let removed = events.clone();
+    inventory[0].removed_handler = Some(Box::new(move |i: &DefaultItemInstance<DefaultItem>| {
+        removed.borrow_mut().push(format!("removed {}", i.quant()))
+    }));
+    let changed = events.clone();
+    inventory[0].event_handler = Some(Box::new(
+        move |e: SlotEvent<DefaultItemInstance<DefaultItem>>| {
+            changed.borrow_mut().push(format!("{:?}", e))
+        },
+    ));
+    let torches = DefaultItemInstance::new_unchecked(TORCH_INST.clone().unwrap().item(), 150);
+    let left = add_to_inventory(&mut inventory, torches).unwrap();
+    assert_eq!(left.quant(), 50);
+    assert_eq!(inventory[0].quantity(), 100);
+    assert_eq!(*events.borrow(), vec!["added 100"]);
 }
@@ -0,0 +1,199 @@
+#![cfg(feature = "serde")]
+use game_inventory::errors::{
+    CraftError, CursorError, DeserializeError, InventoryError, QuantityError,
+};
+use game_inventory::helpers::{CursorSlot, DropResult, ItemSort};
+use game_inventory::inventory::{Inventory, InventoryConstraints};
+use game_inventory::samples::{DefaultItem, DefaultItemInstance, DefaultSlot, TORCH, TORCH_INST};
+use game_inventory::traits::{Item, ItemInstance};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+type TestInstance = DefaultItemInstance<DefaultItem<'static>>;
+type TestSlot = DefaultSlot<'static, DefaultItem<'static>, TestInstance>;
+
+fn assert_serde<'de, T: Serialize + Deserialize<'de>>() {}
+
+#[test]
+fn public_types_implement_serde() {
+    assert_serde::<DefaultItem>();
+    assert_serde::<TestInstance>();
+    assert_serde::<TestSlot>();
+    assert_serde::<Inventory<TestSlot>>();
+    assert_serde::<CursorSlot<TestInstance>>();
+    assert_serde::<InventoryConstraints>();
+    assert_serde::<ItemSort>();
+    assert_serde::<DropResult>();
+    assert_serde::<QuantityError>();
+    assert_serde::<InventoryError>();
+    assert_serde::<CursorError>();
+    assert_serde::<CraftError>();
+    assert_serde::<DeserializeError>();
+}
+
+#[test]
+fn default_item_snake_case() {
+    let json = serde_json::to_string(&TORCH).unwrap();
+    assert_eq!(
+        json,
+        r#"{"name":"torch","max_quantity":100,"item_type":"tool"}"#
+    );
+    let item: DefaultItem = serde_json::from_str(&json).unwrap();
+    assert_eq!(item.id(), TORCH.id());
+    assert_eq!(item.max_quant(), TORCH.max_quant());
+    assert_eq!(item.item_type, TORCH.item_type);
+}
+
+#[test]
+fn default_item_tags_round_trip() {
+    let item = DefaultItem::new("fire potion", 10, "potion").with_tags(&["fire", "consumable"]);
+    let json = serde_json::to_string(&item).unwrap();
+    assert!(json.contains(r#""tags":["fire","consumable"]"#));
+    let item: DefaultItem = serde_json::from_str(&json).unwrap();
+    assert_eq!(item.tags(), &["fire", "consumable"]);
+    assert!(!serde_json::to_string(&TORCH).unwrap().contains("tags"));
+}
+
+#[test]
+fn default_item_description_round_trip() {
+    let item = DefaultItem::new("cheese", 100, "food").with_description("Smells strong.");
+    let json = serde_json::to_string(&item).unwrap();
+    assert!(json.contains(r#""description":"Smells strong.""#));
+    let item: DefaultItem = serde_json::from_str(&json).unwrap();
+    assert_eq!(item.description(), Some("Smells strong."));
+    assert!(item.flavor_text().is_none());
+}
+
+#[test]
+fn default_slot_metadata_defaults_when_missing() {
+    let slot: DefaultSlot<DefaultItem, TestInstance, (bool, u8)> =
+        serde_json::from_str(r#"{"modified":true}"#).unwrap();
+    assert!(slot.item_instance.is_none());
+    assert_eq!(slot.metadata, (false, 0));
+
+    let slot: DefaultSlot<DefaultItem, TestInstance, u8> = DefaultSlot::with_metadata(None, 3);
+    let json = serde_json::to_string(&slot).unwrap();
+    assert!(json.contains(r#""metadata":3"#));
+}
+
+#[test]
+fn default_item_instance_checks_quantity() {
+    let json = serde_json::to_string(&TORCH_INST.clone().unwrap()).unwrap();
+    let torches: DefaultItemInstance<DefaultItem> = serde_json::from_str(&json).unwrap();
+    assert_eq!(torches.quant(), 23);
+
+    let too_many = json.replace("23", "999");
+    let err = serde_json::from_str::<DefaultItemInstance<DefaultItem>>(&too_many).unwrap_err();
+    assert!(err.to_string().contains("exceeds the max quantity"));
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Arrow {
+    max_quantity: u16,
+}
+
+impl Item for Arrow {
+    type Id = &'static str;
+    fn stackable(&self) -> bool {
+        true
+    }
+    fn max_quant(&self) -> u16 {
+        self.max_quantity
+    }
+    fn id(&self) -> &'static str {
+        "arrow"
+    }
+}
+
+/// Loads whatever quantity it is given, like instances read from a game file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Arrows {
+    item: Arc<Arrow>,
+    quantity: u16,
+}
+
+impl ItemInstance<Arrow> for Arrows {
+    fn quant(&self) -> u16 {
+        self.quantity
+    }
+    fn item(&self) -> Arc<Arrow> {
+        self.item.clone()
+    }
+    fn new(item: Arc<Arrow>, quantity: u16) -> Self {
+        Arrows { item, quantity }
+    }
+}
+
+fn saved_inventory() -> Vec<Option<Arrows>> {
+    let arrow = Arc::new(Arrow { max_quantity: 50 });
+    vec![
+        Some(Arrows::new(arrow.clone(), 500)),
+        Some(Arrows::new(arrow.clone(), 0)),
+        Some(Arrows::new(arrow, 20)),
+        None,
+    ]
+}
+
+fn quantities(inventory: &[Option<Arrows>]) -> Vec<Option<u16>> {
+    inventory
+        .iter()
+        .map(|s| s.as_ref().map(|a| a.quant()))
+        .collect()
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn load_inventory_from_json_clamps_quantities() {
+    use game_inventory::serialization::load_inventory_from_json;
+    let json = serde_json::to_string(&saved_inventory()).unwrap();
+    let inventory: Vec<Option<Arrows>> = load_inventory_from_json(&json).unwrap();
+    assert_eq!(
+        quantities(&inventory),
+        vec![Some(50), Some(1), Some(20), None]
+    );
+    assert!(load_inventory_from_json::<_, Arrow, Arrows, Option<Arrows>>("[").is_err());
+}
+
+#[cfg(feature = "bincode")]
+#[test]
+fn load_inventory_binary_clamps_quantities() {
+    use game_inventory::serialization::load_inventory_binary;
+    let bytes = bincode::serialize(&saved_inventory()).unwrap();
+    let inventory: Vec<Option<Arrows>> = load_inventory_binary(&bytes).unwrap();
+    assert_eq!(
+        quantities(&inventory),
+        vec![Some(50), Some(1), Some(20), None]
+    );
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn json_serializer_camel_case() {
+    use game_inventory::serialization::{InventorySerializer, JsonInventorySerializer, KeyCase};
+    use game_inventory::traits::Slot;
+    type ArrowSlot = DefaultSlot<'static, Arrow, DefaultItemInstance<Arrow>>;
+    let arrow = Arc::new(Arrow { max_quantity: 50 });
+    let mut inventory: Vec<ArrowSlot> = vec![
+        DefaultSlot::new(Some(DefaultItemInstance::new(arrow, 20).unwrap())),
+        DefaultSlot::new(None),
+    ];
+    inventory[1].capacity_override = Some(10);
+
+    let snake = JsonInventorySerializer::<Vec<ArrowSlot>>::new();
+    let camel = JsonInventorySerializer::<Vec<ArrowSlot>>::new().with_key_case(KeyCase::Camel);
+    let json = camel.serialize(&inventory);
+    assert_eq!(
+        json,
+        r#"[{"capacityOverride":null,"itemInstance":{"item":{"maxQuantity":50},"quantity":20},"metadata":null,"modified":false},{"capacityOverride":10,"itemInstance":null,"metadata":null,"modified":false}]"#
+    );
+    assert!(snake
+        .serialize(&inventory)
+        .contains(r#""capacity_override":10"#));
+
+    let loaded = camel.deserialize(&json).unwrap();
+    assert_eq!(loaded[0].item_instance().unwrap().quant(), 20);
+    assert_eq!(loaded[1].capacity_override, Some(10));
+    assert!(snake.deserialize(&json).unwrap()[1]
+        .capacity_override
+        .is_none());
+}
@@ -0,0 +1,101 @@
+use game_inventory::errors::{CursorError, InventoryError};
+use game_inventory::helpers::{
+    combine_stack, move_slot, move_slot_in_inventory, move_stack, CursorSlot, DropResult,
+};
+use game_inventory::samples::{
+    DefaultItem, DefaultItemInstance, DefaultSlot, SWORD_INST, TORCH, TORCH_INST,
+};
+use game_inventory::traits::{Item, ItemInstance, Slot};
+use std::sync::Arc;
+
+type TestSlot =
+    DefaultSlot<'static, DefaultItem<'static>, DefaultItemInstance<DefaultItem<'static>>>;
+
+fn torches(quantity: u16) -> Option<DefaultItemInstance<DefaultItem<'static>>> {
+    Some(DefaultItemInstance::new(Arc::new(TORCH.clone()), quantity).unwrap())
+}
+
+#[test]
+fn cursor_respects_slot_filter() {
+    let mut slot: TestSlot = DefaultSlot::new(None);
+    slot.item_filter = Some(|item| !item.stackable());
+    let mut cursor = CursorSlot(TORCH_INST.clone());
+    assert_eq!(
+        cursor.drop_onto(&mut slot),
+        Err(CursorError::ItemNotAccepted)
+    );
+    assert!(slot.is_empty());
+    assert_eq!(cursor.0.as_ref().unwrap().quant(), 23);
+
+    let mut cursor = CursorSlot(SWORD_INST.clone());
+    assert_eq!(cursor.drop_onto(&mut slot), Ok(DropResult::Placed));
+}
+
+#[test]
+fn cursor_respects_slot_capacity() {
+    let mut slot: TestSlot = DefaultSlot::new(torches(5));
+    slot.capacity_override = Some(10);
+    let mut cursor = CursorSlot(torches(40));
+    assert_eq!(
+        cursor.drop_onto(&mut slot),
+        Ok(DropResult::MergedWithRemainder)
+    );
+    assert_eq!(slot.quantity(), 10);
+    assert_eq!(cursor.0.as_ref().unwrap().quant(), 35);
+
+    let mut empty: TestSlot = DefaultSlot::new(None);
+    empty.capacity_override = Some(10);
+    assert_eq!(
+        cursor.drop_onto(&mut empty),
+        Ok(DropResult::MergedWithRemainder)
+    );
+    assert_eq!(empty.quantity(), 10);
+    assert_eq!(cursor.0.as_ref().unwrap().quant(), 25);
+}
+
+#[test]
+fn cursor_exact_fill_is_merged() {
+    let mut slot: TestSlot = DefaultSlot::new(torches(90));
+    let mut cursor = CursorSlot(torches(10));
+    assert_eq!(cursor.drop_onto(&mut slot), Ok(DropResult::Merged));
+    assert!(cursor.is_empty());
+    assert_eq!(slot.quantity(), 100);
+}
+
+#[test]
+fn combine_stack_exact_fill_leaves_nothing() {
+    let (left, merged) = combine_stack((torches(30), torches(70))).ok().unwrap();
+    assert!(left.is_none());
+    assert_eq!(merged.unwrap().quant(), 100);
+}
+
+#[test]
+fn moves_respect_slot_filter() {
+    let mut inventory: Vec<TestSlot> = vec![
+        DefaultSlot::new(TORCH_INST.clone()),
+        DefaultSlot::new(None),
+        DefaultSlot::new(torches(10)),
+    ];
+    inventory[1].item_filter = Some(|item| !item.stackable());
+    inventory[2].item_filter = Some(|item| !item.stackable());
+    assert_eq!(
+        move_slot_in_inventory(&mut inventory, 0, 1),
+        Err(InventoryError::ItemNotAccepted)
+    );
+    assert_eq!(
+        move_stack(&mut inventory, 0, 1),
+        Err(InventoryError::ItemNotAccepted)
+    );
+    assert_eq!(
+        move_stack(&mut inventory, 0, 2),
+        Err(InventoryError::ItemNotAccepted)
+    );
+    let (source, dest) = inventory.split_at_mut(1);
+    assert_eq!(
+        move_slot(&mut source[0], &mut dest[0]),
+        Err(InventoryError::ItemNotAccepted)
+    );
+    assert_eq!(inventory[0].quantity(), 23);
+    assert!(inventory[1].is_empty());
+    assert_eq!(inventory[2].quantity(), 10);
+}
@@ -0,0 +1,79 @@
+use game_inventory::helpers::{item_total_quantity, ItemSort};
+use game_inventory::inventory::SortedInsertInventory;
+use game_inventory::samples::{DefaultItem, DefaultItemInstance, DefaultSlot, JUNK, SWORD, TORCH};
+use game_inventory::traits::{Item, ItemInstance, Slot};
+use proptest::prelude::*;
+use std::sync::Arc;
+
+type TestSlot =
+    DefaultSlot<'static, DefaultItem<'static>, DefaultItemInstance<DefaultItem<'static>>>;
+
+#[derive(Debug, Clone)]
+enum Op {
+    Add(usize, u16),
+    Remove(usize, u16),
+}
+
+fn items() -> [&'static DefaultItem<'static>; 3] {
+    [&TORCH, &JUNK, &SWORD]
+}
+
+fn instance(item: usize, quant: u16) -> DefaultItemInstance<DefaultItem<'static>> {
+    let item = items()[item];
+    let quant = if item.stackable() { quant } else { 0 };
+    DefaultItemInstance::new_unchecked(Arc::new(item.clone()), quant)
+}
+
+fn op() -> impl Strategy<Value = Op> {
+    prop_oneof![
+        (0..3usize, 1..=100u16).prop_map(|(i, q)| Op::Add(i, q)),
+        (0..3usize, 1..=150u16).prop_map(|(i, q)| Op::Remove(i, q)),
+    ]
+}
+
+fn sort_key() -> impl Strategy<Value = ItemSort> {
+    prop_oneof![
+        Just(ItemSort::Id),
+        Just(ItemSort::Quantity),
+        Just(ItemSort::QuantityDescending),
+    ]
+}
+
+fn is_sorted(inventory: &[TestSlot], sort_key: ItemSort) -> bool {
+    inventory.windows(2).all(|pair| {
+        let (a, b) = (&pair[0], &pair[1]);
+        match sort_key {
+            ItemSort::Id => a.item_id() <= b.item_id(),
+            ItemSort::Quantity => a.quantity() <= b.quantity(),
+            ItemSort::QuantityDescending => a.quantity() >= b.quantity(),
+        }
+    })
+}
+
+proptest! {
+    #[test]
+    fn stays_sorted_after_adds_and_removes(sort_key in sort_key(), ops in prop::collection::vec(op(), 0..40)) {
+        let mut inventory: SortedInsertInventory<TestSlot> = SortedInsertInventory::new(sort_key);
+        let mut totals = [0u32; 3];
+        for op in ops {
+            match op {
+                Op::Add(item, quant) => {
+                    prop_assert!(inventory.add_item(instance(item, quant)).is_none());
+                    totals[item] += if items()[item].stackable() { quant as u32 } else { 1 };
+                }
+                Op::Remove(item, quant) => {
+                    let quant = if items()[item].stackable() { quant } else { 1 };
+                    let left = inventory
+                        .remove_item(DefaultItemInstance::new_unchecked(Arc::new(items()[item].clone()), quant))
+                        .map_or(0, |l| l.quant());
+                    totals[item] -= (quant - left) as u32;
+                }
+            }
+            prop_assert!(is_sorted(&inventory, sort_key));
+            prop_assert!(inventory.iter().all(|s| s.item_instance().is_some()));
+        }
+        for (item, total) in items().iter().zip(totals) {
+            prop_assert_eq!(item_total_quantity(&inventory, item.id()), total);
+        }
+    }
+}